@@ -0,0 +1,427 @@
+use std::collections::HashMap;
+
+use crate::domain_model::bits::Bits;
+use crate::domain_model::board::{Board, MAX_BOARD_SIZE};
+use crate::domain_model::cell::{CellState, ICellState};
+use crate::domain_model::cell_pos::CellPos;
+use crate::domain_model::reversi_state::ReversiState;
+use crate::domain_model::turn::Turn;
+use crate::domain_model::variant::Variant;
+use crate::error::ReversiError;
+
+/// 完全読みできる空きマスの最大数
+pub const MAX_SOLVABLE_EMPTIES: u32 = 18;
+
+// 石差の絶対値より大きい値。探索の窓の初期値に使う
+const DISC_DIFF_BOUND: i32 = (MAX_BOARD_SIZE * MAX_BOARD_SIZE) as i32 + 1;
+
+// この空きマス数より多ければ、相手の着手可能数が少ない手から調べる
+const FASTEST_FIRST_EMPTIES: u32 = 6;
+
+// この空きマス数以上の局面は、読んだ結果を置換表に残す。少ない局面は読み直した方が速い
+const TRANSPOSITION_EMPTIES: u32 = 8;
+
+/// 完全読みの結果
+#[derive(Debug, Clone)]
+pub struct EndgameResult {
+    pub turn: Turn,                       // 読み始めた局面の手番
+    pub disc_diff: i32,                   // 手番側から見た、今のルールでの勝ち越し幅。Standardは石差、Antiは符号を逆にした石差で、勝っていれば正
+    pub best_line: Vec<Option<CellPos>>,  // 最善進行。パスはNone
+}
+
+/// 勝ち負けだけの完全読みの結果。手番側から見た勝敗
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WldResult {
+    Win,
+    Loss,
+    Draw,
+}
+
+/// 現在の局面を最後まで読み切り、最終的な勝ち越し幅と最善進行を求める
+pub fn solve(state: &ReversiState) -> Result<EndgameResult, ReversiError> {
+    let stone = solvable_stone(state)?;
+    let mut solver = Solver::new(state.variant);
+    let disc_diff = solver.negamax(&state.board, stone, -DISC_DIFF_BOUND, DISC_DIFF_BOUND, false);
+
+    Ok(EndgameResult {
+        turn: state.turn,
+        disc_diff,
+        best_line: solver.best_line(&state.board, stone, disc_diff),
+    })
+}
+
+/// 現在の局面を最後まで読み切り、勝ち負けだけを求める。solveより速い
+pub fn solve_wld(state: &ReversiState) -> Result<WldResult, ReversiError> {
+    let stone = solvable_stone(state)?;
+    let score = Solver::new(state.variant).negamax(&state.board, stone, -1, 1, false);
+
+    Ok(if score > 0 {
+        WldResult::Win
+    } else if score < 0 {
+        WldResult::Loss
+    } else {
+        WldResult::Draw
+    })
+}
+
+fn solvable_stone(state: &ReversiState) -> Result<CellState, ReversiError> {
+    let empties = state.board.count_blank_cells() as u32;
+    if empties > MAX_SOLVABLE_EMPTIES {
+        return Err(ReversiError::TooManyEmpties { empties, max: MAX_SOLVABLE_EMPTIES });
+    }
+
+    Ok(match state.turn {
+        Turn::Black => CellState::BlackStone,
+        Turn::White => CellState::WhiteStone,
+    })
+}
+
+/// 置換表の1項目。手番側から見た勝ち越し幅の下限と上限、その局面で最も良かった手のビット番号
+#[derive(Clone, Copy)]
+struct Bound {
+    lower: i32,
+    upper: i32,
+    best: u32,
+}
+
+/// 1回の完全読みで使う状態。同じ局面を読み直さないよう、結果を置換表に残す
+struct Solver {
+    variant: Variant,
+    table: HashMap<(Bits, Bits), Bound>, // キーは手番側の石と相手の石
+}
+
+impl Solver {
+    fn new(variant: Variant) -> Solver {
+        Solver { variant, table: HashMap::new() }
+    }
+
+    /// 勝ち越し幅がdisc_diffになる進行を、1手ずつ確かめながら辿る
+    fn best_line(&mut self, board: &Board, stone: CellState, disc_diff: i32) -> Vec<Option<CellPos>> {
+        let mut line: Vec<Option<CellPos>> = Vec::new();
+        let mut board = *board;
+        let mut stone = stone;
+        let mut score = disc_diff;
+
+        loop {
+            let moves = board.legal_moves_bits(stone);
+            if moves.is_empty() {
+                if board.legal_moves_bits(stone.get_reverse_stone()).is_empty() {
+                    return line;
+                }
+                line.push(None);
+                stone = stone.get_reverse_stone();
+                score = -score;
+                continue;
+            }
+
+            // 勝ち越し幅がscoreになる手を探す。見つからなければ、そこまでの進行を返す
+            let mut children = Children::new(&board, moves, stone, self.best_move(&board, stone));
+            let found = children.as_slice().iter()
+                .find(|(_, next)| -self.negamax(next, stone.get_reverse_stone(), -score - 1, -score + 1, false) == score)
+                .copied();
+            let Some((index, next)) = found else {
+                return line;
+            };
+            line.push(Some(board.index_to_pos(index)));
+            board = next;
+            stone = stone.get_reverse_stone();
+            score = -score;
+        }
+    }
+
+    /// stone側から見た、今のルールでの最終的な勝ち越し幅をネガマックス法で求める。
+    /// alphaとbetaの間に収まらなければ、その外側の値を返す
+    fn negamax(&mut self, board: &Board, stone: CellState, mut alpha: i32, mut beta: i32, passed: bool) -> i32 {
+        let opponent = stone.get_reverse_stone();
+        let empties = board.blank_bits().count_ones();
+        let moves = if empties == 0 { Bits::EMPTY } else { board.legal_moves_bits(stone) };
+        if moves.is_empty() {
+            // 空きマスがないか、両者とも置けなければ終局
+            if empties == 0 || passed {
+                return self.variant.disc_margin(board.stone_bits(stone).count_ones() as i32,
+                    board.stone_bits(opponent).count_ones() as i32);
+            }
+            return -self.negamax(board, opponent, -beta, -alpha, true);
+        }
+
+        // 終局間近は並べ替えも置換表も割に合わないので、そのままの順に読む
+        if empties <= FASTEST_FIRST_EMPTIES {
+            let mut best = -DISC_DIFF_BOUND;
+            for index in moves.iter() {
+                let mut next = *board;
+                next.put_bits(index, board.flip_bits(index, stone), stone);
+                let score = -self.negamax(&next, opponent, -beta, -alpha, false);
+                if score > best {
+                    best = score;
+                    if score > alpha {
+                        alpha = score;
+                        if alpha >= beta {
+                            break;
+                        }
+                    }
+                }
+            }
+            return best;
+        }
+
+        // 置換表の範囲で窓を狭める。範囲だけで決まれば読まない
+        let use_table = empties >= TRANSPOSITION_EMPTIES;
+        let key = (board.stone_bits(stone), board.stone_bits(opponent));
+        let mut known = Bound { lower: -DISC_DIFF_BOUND, upper: DISC_DIFF_BOUND, best: 0 };
+        let mut first = None;
+        if use_table {
+            if let Some(entry) = self.table.get(&key) {
+                if entry.lower >= beta || entry.lower == entry.upper {
+                    return entry.lower;
+                }
+                if entry.upper <= alpha {
+                    return entry.upper;
+                }
+                alpha = alpha.max(entry.lower);
+                beta = beta.min(entry.upper);
+                known = *entry;
+                first = Some(entry.best);
+            }
+        }
+
+        let (window_alpha, window_beta) = (alpha, beta);
+        let mut best = -DISC_DIFF_BOUND;
+        let mut best_index = 0;
+        let mut children = Children::new(board, moves, stone, first);
+        for (i, (index, next)) in children.as_slice().iter().enumerate() {
+            // 最初の手より良いかだけを幅のない窓で確かめ、良ければ読み直す
+            let mut score = if i == 0 { -DISC_DIFF_BOUND } else { -self.negamax(next, opponent, -alpha - 1, -alpha, false) };
+            if i == 0 || (alpha < score && score < beta) {
+                score = -self.negamax(next, opponent, -beta, -alpha, false);
+            }
+            if score > best {
+                best = score;
+                best_index = *index;
+                if score > alpha {
+                    alpha = score;
+                    if alpha >= beta {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if use_table {
+            let lower = if best > window_alpha { best.max(known.lower) } else { known.lower };
+            let upper = if best < window_beta { best.min(known.upper) } else { known.upper };
+            self.table.insert(key, Bound { lower, upper, best: best_index });
+        }
+        best
+    }
+
+    /// 置換表に残っている、stoneの手番でboardの局面の最も良かった手
+    fn best_move(&self, board: &Board, stone: CellState) -> Option<u32> {
+        let key = (board.stone_bits(stone), board.stone_bits(stone.get_reverse_stone()));
+        self.table.get(&key).map(|entry| entry.best)
+    }
+}
+
+// 1つの局面で着手可能な手の最大数。完全読みする局面の空きマス数を超えることはない
+const MAX_CHILDREN: usize = MAX_SOLVABLE_EMPTIES as usize;
+
+/// 着手可能な手と着手後の盤面。探索中に確保し直さないよう固定長の配列に入れる
+struct Children {
+    items: [(u32, Board); MAX_CHILDREN],
+    len: usize,
+}
+
+impl Children {
+    /// 着手可能な手と着手後の盤面を、読む順に並べて作る。firstの手があれば最初に読む
+    fn new(board: &Board, moves: Bits, stone: CellState, first: Option<u32>) -> Children {
+        let mut children = Children { items: [(0, *board); MAX_CHILDREN], len: 0 };
+        for index in moves.iter() {
+            let mut next = *board;
+            next.put_bits(index, board.flip_bits(index, stone), stone);
+            children.items[children.len] = (index, next);
+            children.len += 1;
+        }
+
+        // 空きマスが多いうちは、相手の着手可能数が少なくなる手から読む
+        if board.blank_bits().count_ones() > FASTEST_FIRST_EMPTIES {
+            let opponent = stone.get_reverse_stone();
+            children.as_slice().sort_by_key(|(_, next)| next.legal_moves_bits(opponent).count_ones());
+        }
+        // 置換表で前に最も良かった手は、並びに関係なく最初に読む
+        if let Some(position) = children.as_slice().iter().position(|(index, _)| Some(*index) == first) {
+            children.as_slice()[..=position].rotate_right(1);
+        }
+
+        children
+    }
+
+    fn as_slice(&mut self) -> &mut [(u32, Board)] {
+        &mut self.items[..self.len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 枝刈りせずに全ての進行を読んだ、stone側から見たvariantでの勝ち越し幅
+    fn minimax(board: &Board, stone: CellState, passed: bool, variant: Variant) -> i32 {
+        let opponent = stone.get_reverse_stone();
+        let moves = board.legal_moves_bits(stone);
+        if moves.is_empty() {
+            if passed {
+                return variant.disc_margin(board.stone_bits(stone).count_ones() as i32,
+                    board.stone_bits(opponent).count_ones() as i32);
+            }
+            return -minimax(board, opponent, true, variant);
+        }
+        moves.iter()
+            .map(|index| {
+                let mut next = *board;
+                next.put_bits(index, board.flip_bits(index, stone), stone);
+                -minimax(&next, opponent, false, variant)
+            })
+            .max()
+            .unwrap()
+    }
+
+    // 初期配置から決まった規則で手を選び、空きマスがemptiesになった局面を作る
+    fn endgame_state(seed: u32, empties: u32) -> ReversiState {
+        let mut state = ReversiState::new();
+        let mut stone = CellState::BlackStone;
+        let mut turn = Turn::Black;
+        let mut passed = false;
+        let mut step = seed;
+        while state.board.count_blank_cells() as u32 > empties {
+            let moves = state.board.legal_moves_bits(stone);
+            if moves.is_empty() {
+                assert!(!passed, "空きマスが残ったまま終局しました");
+                passed = true;
+            } else {
+                passed = false;
+                step = step.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                let nth = (step >> 16) % moves.count_ones();
+                let index = moves.iter().nth(nth as usize).unwrap();
+                let flips = state.board.flip_bits(index, stone);
+                state.board.put_bits(index, flips, stone);
+            }
+            stone = stone.get_reverse_stone();
+            turn = match turn {
+                Turn::Black => Turn::White,
+                Turn::White => Turn::Black,
+            };
+        }
+        state.turn = turn;
+        state
+    }
+
+    fn stone_of(turn: Turn) -> CellState {
+        match turn {
+            Turn::Black => CellState::BlackStone,
+            Turn::White => CellState::WhiteStone,
+        }
+    }
+
+    #[test]
+    fn solve_matches_full_minimax() {
+        for seed in 1..6 {
+            let state = endgame_state(seed, 9);
+            let result = solve(&state).unwrap();
+            let expected = minimax(&state.board, stone_of(state.turn), false, Variant::Standard);
+            assert_eq!(result.disc_diff, expected, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn solve_wld_agrees_with_solve() {
+        for seed in 1..6 {
+            let state = endgame_state(seed, 12);
+            let disc_diff = solve(&state).unwrap().disc_diff;
+            let expected = if disc_diff > 0 {
+                WldResult::Win
+            } else if disc_diff < 0 {
+                WldResult::Loss
+            } else {
+                WldResult::Draw
+            };
+            assert_eq!(solve_wld(&state).unwrap(), expected, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn best_line_reaches_the_solved_score() {
+        let state = endgame_state(3, 12);
+        let result = solve(&state).unwrap();
+        assert!(!result.best_line.is_empty());
+
+        let mut board = state.board;
+        let mut stone = stone_of(state.turn);
+        for put in result.best_line.iter() {
+            if let Some(pos) = put {
+                let index = board.pos_to_index(pos.row as usize, pos.col as usize);
+                assert!(board.legal_moves_bits(stone).contains(index));
+                board.put_bits(index, board.flip_bits(index, stone), stone);
+            }
+            stone = stone.get_reverse_stone();
+        }
+        let own = stone_of(state.turn);
+        let disc_diff = board.stone_bits(own).count_ones() as i32 - board.stone_bits(own.get_reverse_stone()).count_ones() as i32;
+        assert_eq!(disc_diff, result.disc_diff);
+        assert!(!board.has_legal_move(CellState::BlackStone).unwrap() && !board.has_legal_move(CellState::WhiteStone).unwrap());
+    }
+
+    #[test]
+    fn anti_solve_matches_full_minimax() {
+        for seed in 1..6 {
+            let mut state = endgame_state(seed, 9);
+            state.variant = Variant::Anti;
+            let result = solve(&state).unwrap();
+            let expected = minimax(&state.board, stone_of(state.turn), false, Variant::Anti);
+            assert_eq!(result.disc_diff, expected, "seed {}", seed);
+
+            // 最善進行の終局図は、石の少ない方が勝ち越し幅だけ勝っている
+            let mut board = state.board;
+            let mut stone = stone_of(state.turn);
+            for put in result.best_line.iter() {
+                if let Some(pos) = put {
+                    let index = board.pos_to_index(pos.row as usize, pos.col as usize);
+                    board.put_bits(index, board.flip_bits(index, stone), stone);
+                }
+                stone = stone.get_reverse_stone();
+            }
+            let own = stone_of(state.turn);
+            let disc_diff = board.stone_bits(own).count_ones() as i32 - board.stone_bits(own.get_reverse_stone()).count_ones() as i32;
+            assert_eq!(-disc_diff, result.disc_diff, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn anti_solve_wld_agrees_with_solve() {
+        for seed in 1..6 {
+            let mut state = endgame_state(seed, 12);
+            state.variant = Variant::Anti;
+            let disc_diff = solve(&state).unwrap().disc_diff;
+            let expected = if disc_diff > 0 {
+                WldResult::Win
+            } else if disc_diff < 0 {
+                WldResult::Loss
+            } else {
+                WldResult::Draw
+            };
+            assert_eq!(solve_wld(&state).unwrap(), expected, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn solves_4x4_initial_position() {
+        let state = ReversiState::with_position(Board::with_size(4).unwrap(), Turn::Black);
+        // 4x4は互いに最善を尽くすと白の8石差勝ち
+        assert_eq!(solve(&state).unwrap().disc_diff, -8);
+        assert_eq!(solve_wld(&state).unwrap(), WldResult::Loss);
+    }
+
+    #[test]
+    fn too_many_empties_is_an_error() {
+        assert!(solve(&ReversiState::new()).is_err());
+        assert!(solve_wld(&ReversiState::new()).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use crate::domain_model::bits::Bits;
+use crate::domain_model::board::{Board, MAX_BOARD_SIZE};
+use crate::domain_model::cell::{CellState, ICellState};
+use crate::domain_model::variant::Variant;
+
+// 辺からの距離ごとの重み。隅は高く、隅の隣(X打ち, C打ち)は低くする。
+// [行の辺からの距離][列の辺からの距離]で、距離3以上は3とみなす
+const EDGE_WEIGHTS: [[i32; 4]; 4] = [
+    [100, -20,  10,   5],
+    [-20, -50,  -2,  -2],
+    [ 10,  -2,  -1,  -1],
+    [  5,  -2,  -1,  -1],
+];
+
+const fn cell_weights(size: usize) -> [i32; MAX_BOARD_SIZE * MAX_BOARD_SIZE] {
+    let mut weights = [0; MAX_BOARD_SIZE * MAX_BOARD_SIZE];
+    let mut index = 0;
+    while index < size * size {
+        weights[index] = EDGE_WEIGHTS[edge_distance(index / size, size)][edge_distance(index % size, size)];
+        index += 1;
+    }
+    weights
+}
+
+const fn edge_distance(i: usize, size: usize) -> usize {
+    let distance = if i < size - 1 - i { i } else { size - 1 - i };
+    if distance < 3 { distance } else { 3 }
+}
+
+// 盤面の大きさ(1辺が4, 6, ..., 16)ごとの、ビット番号順のセルの重み
+static CELL_WEIGHTS: [[i32; MAX_BOARD_SIZE * MAX_BOARD_SIZE]; 7] = [
+    cell_weights(4), cell_weights(6), cell_weights(8), cell_weights(10),
+    cell_weights(12), cell_weights(14), cell_weights(16),
+];
+
+// 着手可能数1つあたりの評価値
+const MOBILITY_WEIGHT: i32 = 8;
+
+// 終局時の石差1つあたりの評価値。途中局面のどの評価値よりも大きくする
+pub const FINAL_DISC_WEIGHT: i32 = 10000;
+
+/// 評価関数の種類
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Evaluation {
+    DiscCount,  // 石の数の差だけ
+    Positional, // セルごとの重み
+    Full,       // セルごとの重みと着手可能数
+}
+
+/// stone側から見た局面の評価値
+pub fn evaluate(board: &Board, stone: CellState, evaluation: Evaluation, variant: Variant) -> i32 {
+    let opponent = stone.get_reverse_stone();
+    // アンチリバーシでは石を持つほど不利なので、石の数と位置の評価を反転する。
+    // 着手可能数はどちらのルールでも多い方が有利
+    let sign = match variant {
+        Variant::Standard => 1,
+        Variant::Anti => -1,
+    };
+    match evaluation {
+        Evaluation::DiscCount => {
+            sign * (board.stone_bits(stone).count_ones() as i32 - board.stone_bits(opponent).count_ones() as i32)
+        },
+        Evaluation::Positional => {
+            sign * (weight_sum(board, board.stone_bits(stone)) - weight_sum(board, board.stone_bits(opponent)))
+        },
+        Evaluation::Full => {
+            let position = weight_sum(board, board.stone_bits(stone)) - weight_sum(board, board.stone_bits(opponent));
+            let mobility = board.legal_moves_bits(stone).count_ones() as i32
+                - board.legal_moves_bits(opponent).count_ones() as i32;
+            sign * position + mobility * MOBILITY_WEIGHT
+        },
+    }
+}
+
+/// 終局時のstone側から見た評価値
+pub fn evaluate_final(board: &Board, stone: CellState, variant: Variant) -> i32 {
+    variant.disc_margin(board.stone_bits(stone).count_ones() as i32,
+        board.stone_bits(stone.get_reverse_stone()).count_ones() as i32) * FINAL_DISC_WEIGHT
+}
+
+fn weight_sum(board: &Board, bits: Bits) -> i32 {
+    let weights = &CELL_WEIGHTS[board.size() / 2 - 2];
+    bits.iter().map(|index| weights[index as usize]).sum()
+}
@@ -0,0 +1,25 @@
+use crate::ai::evaluator::Evaluation;
+use crate::domain_model::computer_setting::AiLevel;
+
+/// 強さごとの探索の設定
+#[derive(Debug, Clone, Copy)]
+pub struct SearchSettings {
+    pub depth: u32,
+    pub evaluation: Evaluation,
+    // 候補手の評価値に加える乱数の幅。0なら常に最善手を選ぶ
+    pub randomness: i32,
+    // 空きマスがこの数以下になったら完全読みで手を選ぶ。0なら完全読みしない
+    pub endgame_empties: u32,
+}
+
+pub fn search_settings(level: AiLevel) -> SearchSettings {
+    // ランダムと貪欲は専用のプレーヤーが打つ。探索で使う場合は、1手読みで同じように選ぶ
+    match level {
+        AiLevel::Random => SearchSettings { depth: 1, evaluation: Evaluation::DiscCount, randomness: 1000, endgame_empties: 0 },
+        AiLevel::Greedy => SearchSettings { depth: 1, evaluation: Evaluation::DiscCount, randomness: 0, endgame_empties: 0 },
+        AiLevel::Beginner => SearchSettings { depth: 1, evaluation: Evaluation::DiscCount, randomness: 3, endgame_empties: 0 },
+        AiLevel::Intermediate => SearchSettings { depth: 3, evaluation: Evaluation::Positional, randomness: 20, endgame_empties: 0 },
+        AiLevel::Strong => SearchSettings { depth: 6, evaluation: Evaluation::Full, randomness: 0, endgame_empties: 12 },
+        AiLevel::Maximum => SearchSettings { depth: 8, evaluation: Evaluation::Full, randomness: 0, endgame_empties: 16 },
+    }
+}
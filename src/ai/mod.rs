@@ -0,0 +1,4 @@
+pub mod evaluator;
+pub mod search;
+pub mod level;
+pub mod endgame;
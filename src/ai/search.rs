@@ -0,0 +1,122 @@
+use rand::Rng;
+
+use crate::ai::evaluator::{self, Evaluation};
+use crate::ai::level;
+use crate::ai::endgame;
+use crate::domain_model::bits::Bits;
+use crate::domain_model::board::Board;
+use crate::domain_model::cell::{CellState, ICellState};
+use crate::domain_model::cell_pos::CellPos;
+use crate::domain_model::reversi_state::ReversiState;
+use crate::domain_model::turn::Turn;
+use crate::domain_model::computer_setting::AiLevel;
+use crate::domain_model::variant::Variant;
+
+const INFINITY: i32 = i32::MAX;
+
+/// levelの強さで探索して、現在のターンのプレーヤーの手を選ぶ。
+/// 置ける場所がなければNone
+pub fn choose_move(state: &ReversiState, level: AiLevel) -> Option<CellPos> {
+    let settings = level::search_settings(level);
+
+    // 終盤は完全読みで最善手を選ぶ
+    if (state.board.count_blank_cells() as u32) <= settings.endgame_empties {
+        if let Ok(result) = endgame::solve(state) {
+            if let Some(Some(pos)) = result.best_line.first() {
+                return Some(pos.clone());
+            }
+        }
+    }
+
+    let mut rng = rand::thread_rng();
+    if settings.randomness == 0 {
+        return search_best_move(state, settings.depth, settings.evaluation);
+    }
+
+    // 候補手ごとに評価値を求め、乱数を加えた上で最も良い手を選ぶ
+    let stone = match state.turn {
+        Turn::Black => CellState::BlackStone,
+        Turn::White => CellState::WhiteStone,
+    };
+    let board = &state.board;
+    let moves = board.legal_moves_bits(stone);
+    ordered_moves(board, moves).into_iter()
+        .map(|index| {
+            let score = -negamax(&play(board, index, stone), stone.get_reverse_stone(),
+                settings.depth.saturating_sub(1), -INFINITY, INFINITY, false, settings.evaluation, state.variant);
+            let noise = rng.gen_range(-settings.randomness..=settings.randomness);
+            (score.saturating_add(noise), index)
+        })
+        .max_by_key(|&(score, _)| score)
+        .map(|(_, index)| board.index_to_pos(index))
+}
+
+/// depth手先までアルファベータ探索して、現在のターンのプレーヤーの最善手を返す。
+/// 置ける場所がなければNone
+pub fn search_best_move(state: &ReversiState, depth: u32, evaluation: Evaluation) -> Option<CellPos> {
+    let stone = match state.turn {
+        Turn::Black => CellState::BlackStone,
+        Turn::White => CellState::WhiteStone,
+    };
+    let board = &state.board;
+    let moves = board.legal_moves_bits(stone);
+    if moves.is_empty() {
+        return None;
+    }
+
+    let mut best_index = moves.trailing_zeros();
+    let mut alpha = -INFINITY;
+    for index in ordered_moves(board, moves) {
+        let score = -negamax(&play(board, index, stone), stone.get_reverse_stone(),
+            depth.saturating_sub(1), -INFINITY, -alpha, false, evaluation, state.variant);
+        if score > alpha {
+            alpha = score;
+            best_index = index;
+        }
+    }
+
+    Some(board.index_to_pos(best_index))
+}
+
+/// stone側から見た局面の評価値をネガマックス法で求める
+#[allow(clippy::too_many_arguments)]
+fn negamax(board: &Board, stone: CellState, depth: u32, mut alpha: i32, beta: i32, passed: bool,
+    evaluation: Evaluation, variant: Variant) -> i32 {
+    let moves = board.legal_moves_bits(stone);
+    if moves.is_empty() {
+        // 両者とも置けなければ終局
+        if passed {
+            return evaluator::evaluate_final(board, stone, variant);
+        }
+        return -negamax(board, stone.get_reverse_stone(), depth, -beta, -alpha, true, evaluation, variant);
+    }
+    if depth == 0 {
+        return evaluator::evaluate(board, stone, evaluation, variant);
+    }
+
+    for index in ordered_moves(board, moves) {
+        let score = -negamax(&play(board, index, stone), stone.get_reverse_stone(),
+            depth - 1, -beta, -alpha, false, evaluation, variant);
+        if score > alpha {
+            alpha = score;
+            if alpha >= beta {
+                break;
+            }
+        }
+    }
+
+    alpha
+}
+
+/// ビット番号indexにstoneを置いた後の盤面
+fn play(board: &Board, index: u32, stone: CellState) -> Board {
+    let mut next = *board;
+    next.put_bits(index, board.flip_bits(index, stone), stone);
+    next
+}
+
+/// 着手可能なビット番号を、隅を先にして列挙する
+fn ordered_moves(board: &Board, moves: Bits) -> Vec<u32> {
+    let corners = board.corner_bits();
+    (moves & corners).iter().chain(moves.without(corners).iter()).collect()
+}
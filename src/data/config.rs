@@ -0,0 +1,32 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use serde::Deserialize;
+
+use crate::data::fileio;
+use crate::error::ReversiError;
+
+// 設定はユーザーごとのデータフォルダのconfig.jsonに書く
+// 例: {"lang": "en"}
+static CONFIG_FILENAME: &str = "config.json";
+
+/// 設定ファイルの内容。書かれていない項目はNone
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub lang: Option<String>, // 表示する言語。"ja"か"en"
+}
+
+/// 設定ファイルの場所
+pub fn config_path() -> PathBuf {
+    fileio::data_dir().join(CONFIG_FILENAME)
+}
+
+/// 設定ファイルを読み込む。ファイルがなければ既定の設定
+pub fn read_config() -> Result<Config, ReversiError> {
+    let path = config_path();
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+    Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+}
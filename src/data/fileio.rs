@@ -1,32 +1,397 @@
-use std::fs::File;
+use std::env;
+use std::fs::{self, File};
 use std::io::{Write, BufRead, BufReader};
+use std::path::{Path, PathBuf};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
 use crate::domain_model::r#move::Move;
 use crate::domain_model::cell_pos::CellPos;
 use crate::domain_model::turn::Turn;
 use crate::domain_model::reversi_state::ReversiState;
-use crate::error::ReversiError;
+use crate::domain_model::computer_setting::{AiLevel, ComputerSetting};
+use crate::domain_model::board::Board;
+use crate::domain_model::game_info::GameInfo;
+use crate::domain_model::variant::Variant;
+use crate::domain_model::game_result::{GameResult, Outcome, EndReason};
+use crate::data::{move_list, position};
+use crate::data::timestamp::Timestamp;
+use crate::error::{Location, ParseErrorKind, ReversiError};
 
-static GAMESTATE_FILENAME: &'static str = "othello_gamestate.txt";
+// ゲームはユーザーごとのデータフォルダに、スロット名.jsonとして保存する
+// 以前はカレントフォルダの1つのファイルに保存していた。そのファイルは読み込みだけできる
+static APP_DIR_NAME: &str = "rust_reversi";
+static SAVE_DIR_NAME: &str = "saves";
+static SAVE_EXTENSION: &str = "json";
+static AUTOSAVE_FILENAME: &str = "autosave.json";
+static LEGACY_JSON_FILENAME: &str = "othello_gamestate.json";
+static LEGACY_GAMESTATE_FILENAME: &str = "othello_gamestate.txt";
 
-pub fn write_file(state: &ReversiState) -> Result<(), Box<dyn std::error::Error>> {
-    let mut file = File::create(GAMESTATE_FILENAME)?;
-    writeln!(file, "{}", state.undo_buffer.len())?;
-    for undo_move in &state.undo_buffer {
-        writeln!(file, "{} {} {}", 
-            turn_to_string( &undo_move.turn ), 
-            undo_move.put_pos.row, undo_move.put_pos.col)?;
+// スロット名の最大文字数
+const MAX_SLOT_NAME_LENGTH: usize = 64;
+
+/// 保存ファイルの形式のバージョン。形式を変えたら上げる
+const SAVE_FORMAT_VERSION: u32 = 1;
+
+/// 保存ファイルから読み取った内容
+pub struct SaveData {
+    pub info: GameInfo,
+    pub computer: Option<ComputerSetting>,
+    pub initial_board: Board,
+    pub initial_turn: Turn,
+    pub variant: Variant,
+    pub result: Option<GameResult>, // 保存した局面でゲームが終わっていた場合の結果
+    pub undo_list: Vec<Move>,
+    pub redo_list: Vec<Move>,
+    pub move_locations: Vec<Location>, // 各手が書かれていたファイル内の位置。undo_list、redo_listの順
+}
+
+// JSON形式の保存ファイル
+#[derive(Serialize, Deserialize)]
+struct SaveFile {
+    version: u32,
+    #[serde(default)]
+    slot: String, // 自動保存したときの保存先のスロット名。スロットのファイルではファイル名を使う
+    black_player: String,
+    white_player: String,
+    started_at: String,
+    saved_at: String,
+    variant: String,
+    initial_position: String, // 局面の文字列(OBF形式)
+    computer: Option<ComputerJson>,
+    result: Option<ResultJson>,
+    undo: Vec<MoveJson>,
+    redo: Vec<MoveJson>, // 進める順
+}
+
+#[derive(Serialize, Deserialize)]
+struct ComputerJson {
+    turn: String,
+    level: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ResultJson {
+    winner: Option<String>, // 引き分けならnull
+    reason: String,
+    black: i16,
+    white: i16,
+    empty: i16,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MoveJson {
+    turn: String,
+    pos: Option<String>, // "f5"のような表記。パスならnull
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    eval: Option<f64>, // GGFから読み込んだ手の評価値
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    time: Option<String>, // GGFから読み込んだ手の消費時間
+}
+
+/// 保存スロットの一覧の1件
+pub struct SaveSlot {
+    pub name: String,
+    pub summary: Option<SlotSummary>, // 読み込めないファイルならNone
+}
+
+/// 保存スロットの内容の要約
+pub struct SlotSummary {
+    pub saved_at: String,
+    pub black_player: String,
+    pub white_player: String,
+    pub move_count: usize, // 保存した局面までに打った手の数。パスは数えない
+}
+
+/// 保存先のフォルダ。環境変数REVERSI_DATA_DIRがあればそこ、
+/// なければOSごとのユーザーのデータフォルダの下に作る
+pub fn save_dir() -> PathBuf {
+    data_dir().join(SAVE_DIR_NAME)
+}
+
+pub(crate) fn data_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("REVERSI_DATA_DIR") {
+        return PathBuf::from(dir);
+    }
+    let data_dir = env::var_os("XDG_DATA_HOME").map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    data_dir.join(APP_DIR_NAME)
+}
+
+fn autosave_path() -> PathBuf {
+    data_dir().join(AUTOSAVE_FILENAME)
+}
+
+/// スロット名として使えるか確かめる。ファイル名になるので、パスの区切りなどは使えない
+pub fn validate_slot_name(name: &str) -> Result<(), ReversiError> {
+    if name.trim().is_empty() {
+        return Err(ReversiError::EmptySlotName);
+    }
+    if name.chars().count() > MAX_SLOT_NAME_LENGTH {
+        return Err(ReversiError::SlotNameTooLong { max: MAX_SLOT_NAME_LENGTH });
+    }
+    if name.starts_with('.') || name.chars().any(|c| c.is_control() || "/\\:*?\"<>|".contains(c)) {
+        return Err(ReversiError::InvalidSlotName(name.to_string()));
+    }
+    Ok(())
+}
+
+fn slot_path(name: &str) -> Result<PathBuf, ReversiError> {
+    validate_slot_name(name)?;
+    Ok(save_dir().join(format!("{}.{}", name, SAVE_EXTENSION)))
+}
+
+/// 保存スロットの一覧。新しく保存したものから並べる
+pub fn list_slots() -> Result<Vec<SaveSlot>, ReversiError> {
+    let dir = save_dir();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut slots: Vec<SaveSlot> = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().map(|e| e != SAVE_EXTENSION).unwrap_or(true) {
+            continue;
+        }
+        let name = match path.file_stem() {
+            Some(stem) => stem.to_string_lossy().to_string(),
+            None => continue,
+        };
+        let summary = read_save_file(&path).ok().map(|save_file| SlotSummary {
+            saved_at: save_file.saved_at,
+            black_player: save_file.black_player,
+            white_player: save_file.white_player,
+            move_count: save_file.undo.iter().filter(|mv| mv.pos.is_some()).count(),
+        });
+        slots.push(SaveSlot { name, summary });
+    }
+    slots.sort_by(|a, b| {
+        let saved_at = |slot: &SaveSlot| slot.summary.as_ref().map(|s| s.saved_at.clone()).unwrap_or_default();
+        saved_at(b).cmp(&saved_at(a)).then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(slots)
+}
+
+/// 保存スロットがあるか
+pub fn slot_exists(name: &str) -> bool {
+    slot_path(name).map(|path| path.exists()).unwrap_or(false)
+}
+
+/// 保存スロットを削除する
+pub fn delete_slot(name: &str) -> Result<(), ReversiError> {
+    fs::remove_file(slot_path(name)?)?;
+    Ok(())
+}
+
+/// 保存スロットの名前を変える。変更後の名前が既にあればエラー
+pub fn rename_slot(name: &str, new_name: &str) -> Result<(), ReversiError> {
+    let path = slot_path(name)?;
+    let new_path = slot_path(new_name)?;
+    if !path.exists() {
+        return Err(ReversiError::SlotNotFound(name.to_string()));
     }
-    for redo_move in &state.redo_buffer {
-        writeln!(file, "{} {} {}", 
-            turn_to_string( &redo_move.turn ),
-            redo_move.put_pos.row, redo_move.put_pos.col)?;
+    if new_path.exists() {
+        return Err(ReversiError::SlotExists(new_name.to_string()));
     }
-    
+    fs::rename(path, new_path)?;
     Ok(())
 }
 
+/// ゲーム状態をJSON形式でスロットに保存する。resultは現在の局面でゲームが終わっていればその結果
+pub fn write_slot(name: &str, state: &ReversiState, result: Option<&GameResult>) -> Result<(), ReversiError> {
+    write_atomically(&slot_path(name)?, &to_save_file(state, result))
+}
+
+/// 中断に備えて、ゲーム状態を自動保存する
+pub fn write_autosave(state: &ReversiState, result: Option<&GameResult>) -> Result<(), ReversiError> {
+    write_atomically(&autosave_path(), &to_save_file(state, result))
+}
+
+/// 自動保存したゲームがあるか
+pub fn autosave_exists() -> bool {
+    autosave_path().exists()
+}
+
+/// 自動保存したゲームを読み込む
+pub fn read_autosave() -> Result<SaveData, ReversiError> {
+    read_json_file(&autosave_path())
+}
+
+/// 自動保存したゲームを削除する。なければ何もしない
+pub fn delete_autosave() -> Result<(), ReversiError> {
+    let path = autosave_path();
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// 一時ファイルに書き出してから名前を変える。書き込み中に止まっても元のファイルは壊れない
+fn write_atomically(path: &Path, save_file: &SaveFile) -> Result<(), ReversiError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+
+    let mut file = File::create(&tmp_path)?;
+    serde_json::to_writer_pretty(&mut file, save_file)?;
+    writeln!(file)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+fn to_save_file(state: &ReversiState, result: Option<&GameResult>) -> SaveFile {
+    SaveFile {
+        version: SAVE_FORMAT_VERSION,
+        slot: state.info.slot.clone(),
+        black_player: state.info.black_player.clone(),
+        white_player: state.info.white_player.clone(),
+        started_at: state.info.started_at.clone(),
+        saved_at: Timestamp::now().format_iso8601(),
+        variant: variant_to_string(state.variant).to_string(),
+        initial_position: position::format_position(&state.initial_board, state.initial_turn),
+        computer: state.computer.map(|computer| ComputerJson {
+            turn: turn_to_string(&computer.turn).to_lowercase(),
+            level: level_to_string(computer.level).to_lowercase(),
+        }),
+        result: result.map(|result| ResultJson {
+            winner: match result.outcome {
+                Outcome::Win(turn) => Some(turn_to_string(&turn).to_lowercase()),
+                Outcome::Draw => None,
+            },
+            reason: reason_to_string(result.end_reason).to_string(),
+            black: result.black_count,
+            white: result.white_count,
+            empty: result.empty_count,
+        }),
+        undo: state.undo_buffer.iter().map(move_to_json).collect(),
+        // redoバッファは末尾から取り出すので、逆順に書き出す
+        redo: state.redo_buffer.iter().rev().map(move_to_json).collect(),
+    }
+}
+
+/// スロットに保存したゲームを読み込む
+pub fn read_slot(name: &str) -> Result<SaveData, ReversiError> {
+    let path = slot_path(name)?;
+    if !path.exists() {
+        return Err(ReversiError::SlotNotFound(name.to_string()));
+    }
+    read_json_file(&path)
+}
+
+/// 以前の保存ファイルがカレントフォルダにあれば、そのファイル名
+pub fn legacy_file_name() -> Option<&'static str> {
+    [LEGACY_JSON_FILENAME, LEGACY_GAMESTATE_FILENAME].iter()
+        .copied()
+        .find(|name| Path::new(name).exists())
+}
+
+/// カレントフォルダの以前の保存ファイルを読み込む。JSON形式のファイルがなければテキスト形式のファイルを読む
+pub fn read_legacy_file() -> Result<SaveData, ReversiError> {
+    if Path::new(LEGACY_JSON_FILENAME).exists() {
+        read_json_file(Path::new(LEGACY_JSON_FILENAME))
+    } else {
+        read_legacy_txt_file()
+    }
+}
+
+fn read_save_file(path: &Path) -> Result<SaveFile, ReversiError> {
+    let save_file: SaveFile = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+    if save_file.version > SAVE_FORMAT_VERSION {
+        return Err(ParseErrorKind::UnsupportedVersion(save_file.version).into());
+    }
+    Ok(save_file)
+}
+
+fn read_json_file(path: &Path) -> Result<SaveData, ReversiError> {
+    let save_file = read_save_file(path)?;
+    let variant = string_to_variant(&save_file.variant)
+        .ok_or_else(|| ParseErrorKind::UnsupportedVariant(save_file.variant.clone()))?;
+
+    let (initial_board, initial_turn) = position::parse_position(&save_file.initial_position)?;
+    let computer = match save_file.computer {
+        Some(computer) => Some(ComputerSetting {
+            turn: string_to_turn(&computer.turn)?,
+            level: string_to_level(&computer.level.to_uppercase())
+                .ok_or_else(|| ParseErrorKind::InvalidValue { field: "level", value: computer.level.clone() })?,
+        }),
+        None => None,
+    };
+    let result = match save_file.result {
+        Some(result) => {
+            let winner = result.winner.as_deref().map(string_to_turn).transpose()?;
+            Some(GameResult {
+                outcome: winner.map(Outcome::Win).unwrap_or(Outcome::Draw),
+                black_count: result.black,
+                white_count: result.white,
+                empty_count: result.empty,
+                end_reason: string_to_reason(&result.reason, winner)?,
+                variant,
+            })
+        },
+        None => None,
+    };
+
+    Ok(SaveData {
+        info: GameInfo {
+            black_player: save_file.black_player,
+            white_player: save_file.white_player,
+            started_at: save_file.started_at,
+            slot: save_file.slot,
+        },
+        computer,
+        initial_board,
+        initial_turn,
+        variant,
+        result,
+        undo_list: json_to_moves(&save_file.undo, "undo")?,
+        redo_list: json_to_moves(&save_file.redo, "redo")?,
+        move_locations: (0..save_file.undo.len()).map(|i| Location::Entry { list: "undo", index: i + 1 })
+            .chain((0..save_file.redo.len()).map(|i| Location::Entry { list: "redo", index: i + 1 }))
+            .collect(),
+    })
+}
+
+fn move_to_json(mv: &Move) -> MoveJson {
+    MoveJson {
+        turn: turn_to_string(&mv.turn).to_lowercase(),
+        pos: mv.put_pos.as_ref().map(move_list::format_pos),
+        eval: mv.eval,
+        time: mv.time.clone(),
+    }
+}
+
+fn json_to_moves(moves: &[MoveJson], list_name: &'static str) -> Result<Vec<Move>, ReversiError> {
+    moves.iter()
+        .enumerate()
+        .map(|(i, mv)| json_to_move(mv)
+            .map_err(|e| e.at(Location::Entry { list: list_name, index: i + 1 })))
+        .collect()
+}
+
+fn json_to_move(mv: &MoveJson) -> Result<Move, ReversiError> {
+    let put_pos = match &mv.pos {
+        Some(pos) => match move_list::parse_moves(pos).ok().as_deref() {
+            Some([pos]) => Some(pos.clone()),
+            _ => return Err(ParseErrorKind::InvalidMove(pos.clone()).into()),
+        },
+        None => None,
+    };
+    Ok(Move {
+        turn: string_to_turn(&mv.turn)?,
+        put_pos,
+        flipped_pos_list: Vec::new(),
+        eval: mv.eval,
+        time: mv.time.clone(),
+    })
+}
+
 fn turn_to_string(turn: &Turn) -> &str {
     match turn {
         Turn::Black => "BLACK",
@@ -34,40 +399,143 @@ fn turn_to_string(turn: &Turn) -> &str {
     }
 }
 
-// ファイル読み込みを行い、undo buffer, redo bufferを作成する
-pub fn read_file() -> Result<(Vec<Move>, Vec<Move>), Box<dyn std::error::Error>> {
+fn string_to_turn(s: &str) -> Result<Turn, ReversiError> {
+    match s.to_uppercase().as_str() {
+        "BLACK" => Ok(Turn::Black),
+        "WHITE" => Ok(Turn::White),
+        _ => Err(ParseErrorKind::InvalidTurn(s.to_string()).into()),
+    }
+}
+
+fn level_to_string(level: AiLevel) -> &'static str {
+    match level {
+        AiLevel::Random => "RANDOM",
+        AiLevel::Greedy => "GREEDY",
+        AiLevel::Beginner => "BEGINNER",
+        AiLevel::Intermediate => "INTERMEDIATE",
+        AiLevel::Strong => "STRONG",
+        AiLevel::Maximum => "MAXIMUM",
+    }
+}
+
+fn string_to_level(s: &str) -> Option<AiLevel> {
+    AiLevel::ALL.iter().copied().find(|level| level_to_string(*level) == s)
+}
+
+fn reason_to_string(reason: EndReason) -> &'static str {
+    match reason {
+        EndReason::BoardFull => "board_full",
+        EndReason::NoMoves => "no_moves",
+        EndReason::Resignation(_) => "resignation",
+        EndReason::Timeout(_) => "timeout",
+    }
+}
+
+/// 投了と時間切れは勝った側の相手が負けた側になる
+fn string_to_reason(s: &str, winner: Option<Turn>) -> Result<EndReason, ReversiError> {
+    let loser = || match winner {
+        Some(Turn::Black) => Ok(Turn::White),
+        Some(Turn::White) => Ok(Turn::Black),
+        None => Err(ReversiError::from(ParseErrorKind::InvalidValue { field: "reason", value: s.to_string() })),
+    };
+    match s {
+        "board_full" => Ok(EndReason::BoardFull),
+        "no_moves" => Ok(EndReason::NoMoves),
+        "resignation" => Ok(EndReason::Resignation(loser()?)),
+        "timeout" => Ok(EndReason::Timeout(loser()?)),
+        _ => Err(ParseErrorKind::InvalidValue { field: "reason", value: s.to_string() }.into()),
+    }
+}
+
+fn variant_to_string(variant: Variant) -> &'static str {
+    match variant {
+        Variant::Standard => "standard",
+        Variant::Anti => "anti",
+    }
+}
+
+fn string_to_variant(s: &str) -> Option<Variant> {
+    Variant::ALL.iter().copied().find(|variant| variant_to_string(*variant) == s)
+}
+
+fn read_legacy_txt_file() -> Result<SaveData, ReversiError> {
+    parse_legacy_txt(BufReader::new(File::open(LEGACY_GAMESTATE_FILENAME)?))
+}
+
+// 以前のテキスト形式のファイル読み込みを行い、undo buffer, redo bufferを作成する
+pub(crate) fn parse_legacy_txt(mut reader: impl BufRead) -> Result<SaveData, ReversiError> {
     let mut undo_list: Vec<Move> = Vec::new();
     let mut redo_list: Vec<Move> = Vec::new();
 
-    let mut reader = BufReader::new(File::open(GAMESTATE_FILENAME)?);
     let mut buf = String::new();
+    let mut first_line_count = 1;
 
-    // undoの個数を読み取り
+    // コンピュータの設定があれば読み取り
     reader.read_line(&mut buf)?;
-    let undo_count: usize = buf.trim().parse()?;
+    let r_computer = Regex::new(r"^COMPUTER (BLACK|WHITE) ([A-Z]+)$").unwrap();
+    let computer = match r_computer.captures(buf.trim()) {
+        Some(cap) => Some(ComputerSetting {
+            turn: if &cap[1] == "BLACK" { Turn::Black } else { Turn::White },
+            level: string_to_level(&cap[2])
+                .ok_or_else(|| ReversiError::from(ParseErrorKind::InvalidValue { field: "level", value: cap[2].to_string() })
+                    .at(Location::Line(first_line_count)))?,
+        }),
+        None => None,
+    };
+    if computer.is_some() {
+        buf.clear();
+        reader.read_line(&mut buf)?;
+        first_line_count += 1;
+    }
+
+    // undoの個数を読み取り
+    let undo_count: usize = buf.trim().parse()
+        .map_err(|_| ReversiError::from(ParseErrorKind::InvalidNumber(buf.trim().to_string()))
+            .at(Location::Line(first_line_count)))?;
 
     // 残りを読み取る。
-    let r = Regex::new(r"^(BLACK|WHITE) ([1-8]) ([1-8])$").unwrap();
+    let r = Regex::new(r"^(BLACK|WHITE) (?:([1-8]) ([1-8])|PASS)$").unwrap();
     let mut moves: Vec<Move> = Vec::new();
-    let mut line_count = 2;
-    for line in reader.lines() {
+    let mut move_locations: Vec<Location> = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line_count = i + first_line_count + 1;
         let content = line?;
-        let cap = r.captures(content.as_str())
-            .ok_or(ReversiError::new(format!("読み取りに失敗しました 行番号={}", line_count)))?;
+        let invalid_line = || ReversiError::from(ParseErrorKind::InvalidMove(content.clone()))
+            .at(Location::Line(line_count));
+        let cap = r.captures(content.as_str()).ok_or_else(invalid_line)?;
         let the_move = Move {
             turn: if cap[1].trim() == "BLACK" { Turn::Black } else { Turn::White },
-            put_pos: CellPos {
-                row: cap[2].parse()?,
-                col: cap[3].parse()?,
+            put_pos: match (cap.get(2), cap.get(3)) {
+                (Some(row), Some(col)) => Some(CellPos {
+                    row: row.as_str().parse().map_err(|_| invalid_line())?,
+                    col: col.as_str().parse().map_err(|_| invalid_line())?,
+                }),
+                _ => None,
             },
             flipped_pos_list: Vec::new(),
+            eval: None,
+            time: None,
         };
         moves.push(the_move);
-        line_count += 1;
+        move_locations.push(Location::Line(line_count));
     }
 
+    if undo_count > moves.len() {
+        return Err(ReversiError::from(ParseErrorKind::UndoCountTooLarge { count: undo_count, moves: moves.len() })
+            .at(Location::Line(first_line_count)));
+    }
     undo_list.extend_from_slice(&moves[0..undo_count]);
     redo_list.extend_from_slice(&moves[undo_count..]);
 
-    Ok((undo_list, redo_list))
+    Ok(SaveData {
+        info: GameInfo::default(),
+        computer,
+        initial_board: Board::new(),
+        initial_turn: Turn::Black,
+        variant: Variant::Standard,
+        result: None,
+        undo_list,
+        redo_list,
+        move_locations,
+    })
 }
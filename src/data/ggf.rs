@@ -0,0 +1,354 @@
+use std::fs;
+
+use crate::domain_model::board::Board;
+use crate::domain_model::cell::CellState;
+use crate::domain_model::cell_pos::CellPos;
+use crate::domain_model::turn::Turn;
+use crate::domain_model::variant::Variant;
+use crate::data::move_list;
+use crate::data::timestamp::Timestamp;
+use crate::error::{Location, ParseErrorKind, ReversiError};
+
+// GGF(Generic Game Format)。オンライン対局サーバーや棋譜集で使われる形式
+// (;GM[Othello]PC[場所]DT[日時]PB[黒の名前]PW[白の名前]RB[黒のレーティング]RW[白のレーティング]
+//   TI[持ち時間]TY[盤の種類]RE[結果]BO[8 盤面64文字 手番]B[f5//0.01]W[d6/-1.50/2.34]...;)
+// 盤の種類は盤面の1辺のセル数で、アンチリバーシなら"8a"のように末尾にaを付ける
+
+/// GGFの1手。評価値と消費時間は省略できる
+#[derive(Debug, Clone)]
+pub struct GgfMove {
+    pub turn: Turn,
+    pub put_pos: Option<CellPos>, // パスの場合はNone
+    pub eval: Option<f64>,
+    pub time: Option<String>,
+}
+
+/// GGFの1ゲーム
+#[derive(Debug, Clone)]
+pub struct GgfGame {
+    pub place: String,
+    pub date: String,
+    pub black_player: String,
+    pub white_player: String,
+    pub black_rating: Option<f64>,
+    pub white_rating: Option<f64>,
+    pub time_control: String,
+    pub board_type: String,
+    pub result: String,
+    pub initial_board: Board,
+    pub initial_turn: Turn,
+    pub moves: Vec<GgfMove>,
+}
+
+impl GgfGame {
+    /// 標準の初期配置から始まる、手のないゲーム
+    pub fn new() -> GgfGame {
+        GgfGame {
+            place: String::new(),
+            date: String::new(),
+            black_player: String::new(),
+            white_player: String::new(),
+            black_rating: None,
+            white_rating: None,
+            time_control: String::new(),
+            board_type: "8".to_string(),
+            result: String::new(),
+            initial_board: Board::new(),
+            initial_turn: Turn::Black,
+            moves: Vec::new(),
+        }
+    }
+
+    /// 盤の種類から分かるルール
+    pub fn variant(&self) -> Variant {
+        let flags = self.board_type.trim_start_matches(|c: char| !c.is_ascii_digit())
+            .trim_start_matches(|c: char| c.is_ascii_digit());
+        if flags.contains('a') { Variant::Anti } else { Variant::Standard }
+    }
+}
+
+/// 盤面の1辺のセル数とルールから、盤の種類の表記を作る
+pub fn board_type(size: usize, variant: Variant) -> String {
+    match variant {
+        Variant::Standard => size.to_string(),
+        Variant::Anti => format!("{}a", size),
+    }
+}
+
+impl Default for GgfGame {
+    fn default() -> GgfGame {
+        GgfGame::new()
+    }
+}
+
+/// GGFファイルを読み込む。ファイル内の全てのゲームを返す
+pub fn read_file(path: &str) -> Result<Vec<GgfGame>, ReversiError> {
+    let text = fs::read_to_string(path)?;
+    parse_games(&text)
+}
+
+/// GGFファイルに書き出す
+pub fn write_file(path: &str, game: &GgfGame) -> Result<(), ReversiError> {
+    fs::write(path, format!("{}\n", format_game(game)))?;
+    Ok(())
+}
+
+/// GGFの文字列に含まれる全てのゲームを読み取る
+pub fn parse_games(text: &str) -> Result<Vec<GgfGame>, ReversiError> {
+    let mut games: Vec<GgfGame> = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("(;") {
+        let end = rest[start..].find(";)")
+            .ok_or_else(|| ReversiError::from(ParseErrorKind::UnclosedGame).at(Location::Game(games.len() + 1)))?;
+        let game = parse_game(&rest[start + 2..start + end])
+            .map_err(|e| e.at(Location::Game(games.len() + 1)))?;
+        games.push(game);
+        rest = &rest[start + end + 2..];
+    }
+
+    if games.is_empty() {
+        return Err(ParseErrorKind::NoGame.into());
+    }
+    Ok(games)
+}
+
+/// "(;"と";)"の間を読み取る
+fn parse_game(text: &str) -> Result<GgfGame, ReversiError> {
+    let mut game = GgfGame::new();
+    let mut rest = text.trim_start();
+    while !rest.is_empty() {
+        let open = rest.find('[')
+            .ok_or_else(|| ReversiError::from(ParseErrorKind::MalformedProperty(rest.to_string())))?;
+        let close = rest[open..].find(']')
+            .ok_or_else(|| ReversiError::from(ParseErrorKind::MalformedProperty(rest.to_string())))?;
+        let key = rest[..open].trim();
+        let value = &rest[open + 1..open + close];
+        match key {
+            "GM" if !value.eq_ignore_ascii_case("othello") => {
+                return Err(ParseErrorKind::NotOthello(value.to_string()).into());
+            },
+            "PC" => game.place = value.to_string(),
+            "DT" => game.date = value.to_string(),
+            "PB" => game.black_player = value.to_string(),
+            "PW" => game.white_player = value.to_string(),
+            "RB" => game.black_rating = value.trim().parse().ok(),
+            "RW" => game.white_rating = value.trim().parse().ok(),
+            "TI" => game.time_control = value.to_string(),
+            "TY" => game.board_type = value.to_string(),
+            "RE" => game.result = value.to_string(),
+            "BO" => {
+                let (board, turn) = parse_board(value)?;
+                game.initial_board = board;
+                game.initial_turn = turn;
+            },
+            "B" => game.moves.push(parse_move(Turn::Black, value)?),
+            "W" => game.moves.push(parse_move(Turn::White, value)?),
+            // 対応していないプロパティは読み飛ばす
+            _ => {},
+        }
+        rest = rest[open + close + 1..].trim_start();
+    }
+
+    Ok(game)
+}
+
+/// "8 ---...--- *"の形式の盤面を読み取る。先頭は盤面の1辺のセル数
+fn parse_board(value: &str) -> Result<(Board, Turn), ReversiError> {
+    let mut tokens = value.split_whitespace();
+    let size_text = tokens.next().unwrap_or("");
+    let size = match size_text.parse::<usize>() {
+        Ok(size) if Board::is_valid_size(size) => size,
+        _ => return Err(ParseErrorKind::UnsupportedBoardSize(size_text.to_string()).into()),
+    };
+
+    let cells: Vec<char> = tokens.collect::<String>().chars().collect();
+    if cells.len() != size * size + 1 {
+        return Err(ParseErrorKind::InvalidLength { expected: size * size + 1, actual: cells.len() }.into());
+    }
+
+    let mut board = Board::with_size(size)?;
+    for (i, c) in cells[..size * size].iter().enumerate() {
+        let cell_state = match c {
+            '*' | 'X' | 'x' => CellState::BlackStone,
+            'O' | 'o' => CellState::WhiteStone,
+            '-' | '.' => CellState::BlankCell,
+            _ => return Err(ReversiError::from(ParseErrorKind::InvalidCell(*c))
+                .at(Location::Cell { row: i / size + 1, col: i % size + 1 })),
+        };
+        board.set_cell(i / size + 1, i % size + 1, cell_state)?;
+    }
+    let turn = match cells[size * size] {
+        '*' | 'X' | 'x' => Turn::Black,
+        'O' | 'o' => Turn::White,
+        c => return Err(ParseErrorKind::InvalidTurn(c.to_string()).into()),
+    };
+
+    Ok((board, turn))
+}
+
+/// "f5/評価値/消費時間"の形式の手を読み取る
+fn parse_move(turn: Turn, value: &str) -> Result<GgfMove, ReversiError> {
+    let mut parts = value.split('/');
+    let pos = parts.next().unwrap_or("").trim();
+    let put_pos = if pos.eq_ignore_ascii_case("pa") || pos.eq_ignore_ascii_case("pass") {
+        None
+    } else {
+        match move_list::parse_moves(pos).ok().as_deref() {
+            Some([pos]) => Some(pos.clone()),
+            _ => return Err(ParseErrorKind::InvalidMove(value.to_string()).into()),
+        }
+    };
+    let eval = parts.next().and_then(|eval| eval.trim().parse().ok());
+    let time = parts.next().map(|time| time.trim().to_string()).filter(|time| !time.is_empty());
+
+    Ok(GgfMove { turn, put_pos, eval, time })
+}
+
+/// ゲームをGGFの文字列にする
+pub fn format_game(game: &GgfGame) -> String {
+    let mut text = String::from("(;GM[Othello]");
+    text += &format!("PC[{}]DT[{}]PB[{}]PW[{}]", game.place, game.date, game.black_player, game.white_player);
+    if let Some(rating) = game.black_rating {
+        text += &format!("RB[{:.2}]", rating);
+    }
+    if let Some(rating) = game.white_rating {
+        text += &format!("RW[{:.2}]", rating);
+    }
+    if !game.time_control.is_empty() {
+        text += &format!("TI[{}]", game.time_control);
+    }
+    text += &format!("TY[{}]", game.board_type);
+    if !game.result.is_empty() {
+        text += &format!("RE[{}]", game.result);
+    }
+    text += &format!("BO[{} {} {}]", game.initial_board.size(), format_board(&game.initial_board),
+        turn_char(game.initial_turn));
+
+    for mv in &game.moves {
+        let pos = match &mv.put_pos {
+            Some(pos) => move_list::format_pos(pos).to_uppercase(),
+            None => "PA".to_string(),
+        };
+        let mut value = pos;
+        if mv.eval.is_some() || mv.time.is_some() {
+            value += &format!("/{}/{}",
+                mv.eval.map(|eval| format!("{:.2}", eval)).unwrap_or_default(),
+                mv.time.clone().unwrap_or_default());
+        }
+        text += &format!("{}[{}]", if mv.turn == Turn::Black { "B" } else { "W" }, value);
+    }
+
+    text + ";)"
+}
+
+fn format_board(board: &Board) -> String {
+    let mut cells = String::with_capacity(board.cell_count());
+    for row in 1..=board.size() {
+        for col in 1..=board.size() {
+            cells.push(match board.get_cell(row, col) {
+                Some(CellState::BlackStone) => '*',
+                Some(CellState::WhiteStone) => 'O',
+                _ => '-',
+            });
+        }
+    }
+    cells
+}
+
+fn turn_char(turn: Turn) -> char {
+    match turn {
+        Turn::Black => '*',
+        Turn::White => 'O',
+    }
+}
+
+/// GGFの日時の形式(例: 2003.06.04_18:30:25.UTC)の現在時刻
+pub fn current_date() -> String {
+    let now = Timestamp::now();
+    format!("{:04}.{:02}.{:02}_{:02}:{:02}:{:02}.UTC",
+        now.year, now.month, now.day, now.hour, now.minute, now.second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 4x4の盤で、4手目の後に黒がパスしたゲーム
+    const PASS_RECORD: &str = "(;GM[Othello]PC[GGS/os]DT[2003.06.04_18:30:25.UTC]PB[alpha]PW[beta]\
+        RB[2100.50]RW[1987.00]TI[15:00//02:00]TY[4]RE[-6.000]\
+        BO[4 ---- -OX- -XO- ---- *]B[c4//0.01]W[b4/-1.50/2.34]B[a2]W[d4]B[PA]W[b1];)";
+
+    fn moves_of(game: &GgfGame) -> Vec<(Turn, Option<(i8, i8)>)> {
+        game.moves.iter().map(|mv| (mv.turn, mv.put_pos.as_ref().map(|pos| (pos.row, pos.col)))).collect()
+    }
+
+    fn error_location(result: Result<Vec<GgfGame>, ReversiError>) -> (Location, ReversiError) {
+        match result {
+            Err(ReversiError::At { location, source }) => (location, *source),
+            other => panic!("{:?}", other.map(|games| games.len())),
+        }
+    }
+
+    #[test]
+    fn parses_a_game_record_with_a_pass() {
+        let games = parse_games(&format!("header\n{}\n", PASS_RECORD)).unwrap();
+        assert_eq!(games.len(), 1);
+        let game = &games[0];
+        assert_eq!(game.place, "GGS/os");
+        assert_eq!((game.black_player.as_str(), game.white_player.as_str()), ("alpha", "beta"));
+        assert_eq!((game.black_rating, game.white_rating), (Some(2100.5), Some(1987.0)));
+        assert_eq!(game.time_control, "15:00//02:00");
+        assert_eq!(game.result, "-6.000");
+        assert_eq!(game.variant(), Variant::Standard);
+
+        assert_eq!(game.initial_board, Board::with_size(4).unwrap());
+        assert_eq!(game.initial_turn, Turn::Black);
+        assert_eq!(moves_of(game), vec![
+            (Turn::Black, Some((4, 3))),
+            (Turn::White, Some((4, 2))),
+            (Turn::Black, Some((2, 1))),
+            (Turn::White, Some((4, 4))),
+            (Turn::Black, None),
+            (Turn::White, Some((1, 2))),
+        ]);
+        assert_eq!((game.moves[0].eval, game.moves[0].time.as_deref()), (None, Some("0.01")));
+        assert_eq!((game.moves[1].eval, game.moves[1].time.as_deref()), (Some(-1.5), Some("2.34")));
+    }
+
+    #[test]
+    fn formatted_game_parses_back() {
+        let mut game = parse_games(PASS_RECORD).unwrap().remove(0);
+        game.board_type = board_type(4, Variant::Anti);
+        let parsed = parse_games(&format_game(&game)).unwrap().remove(0);
+        assert_eq!(parsed.variant(), Variant::Anti);
+        assert_eq!(parsed.initial_board, game.initial_board);
+        assert_eq!(moves_of(&parsed), moves_of(&game));
+        assert_eq!(parsed.moves[1].eval, Some(-1.5));
+    }
+
+    #[test]
+    fn rejects_a_malformed_property_in_its_game() {
+        let text = format!("{}(;GM[Othello]PB[alpha]W[d4;)", PASS_RECORD);
+        let (location, source) = error_location(parse_games(&text));
+        assert_eq!(location, Location::Game(2));
+        assert!(matches!(source, ReversiError::Parse(ParseErrorKind::MalformedProperty(_))));
+
+        let (location, source) = error_location(parse_games("(;GM[Othello]TY;)"));
+        assert_eq!(location, Location::Game(1));
+        assert!(matches!(source, ReversiError::Parse(ParseErrorKind::MalformedProperty(_))));
+    }
+
+    #[test]
+    fn rejects_a_board_with_a_blocked_cell() {
+        // GGFには置けないセルの表記がない
+        let (location, source) = error_location(parse_games("(;GM[Othello]BO[4 #--- -OX- -XO- ---- *];)"));
+        assert_eq!(location, Location::Game(1));
+        match source {
+            ReversiError::At { location, source } => {
+                assert_eq!(location, Location::Cell { row: 1, col: 1 });
+                assert!(matches!(*source, ReversiError::Parse(ParseErrorKind::InvalidCell('#'))));
+            },
+            other => panic!("{:?}", other),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::fs;
+
+use crate::domain_model::board::Board;
+use crate::domain_model::cell::CellState;
+use crate::error::{Location, ParseErrorKind, ReversiError};
+
+// 盤面レイアウトファイル。盤面の形と最初の石の配置を、1段を1行として書く
+// #は石を置けないセル、-か.は空き、Xは黒、Oは白。行の;から後はコメントで、空行は無視する
+// 行の数が盤面の1辺のセル数で、どの行もその数の文字を並べる
+// 石を1つも書かなければ、標準の初期配置と同じく中央に4つ石を置く
+// 例: 8x8の八角形の盤面
+// ##----##
+// #------#
+// --------
+// --------
+// --------
+// --------
+// #------#
+// ##----##
+
+/// レイアウトの文字列を盤面にする
+pub fn parse_layout(text: &str) -> Result<Board, ReversiError> {
+    // 行番号と、空白を除いた行の文字
+    let rows: Vec<(usize, Vec<char>)> = text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let line = match line.find(';') {
+                Some(end) => &line[..end],
+                None => line,
+            };
+            (i + 1, line.chars().filter(|c| !c.is_whitespace()).collect::<Vec<char>>())
+        })
+        .filter(|(_, chars)| !chars.is_empty())
+        .collect();
+
+    let size = rows.len();
+    let mut board = Board::with_size(size)?;
+    let mut has_stone = false;
+    for (row, (line, chars)) in rows.iter().enumerate() {
+        if chars.len() != size {
+            return Err(ReversiError::from(ParseErrorKind::InvalidLength { expected: size, actual: chars.len() })
+                .at(Location::Line(*line)));
+        }
+        for (col, c) in chars.iter().enumerate() {
+            let cell_state = match c.to_ascii_uppercase() {
+                'X' => CellState::BlackStone,
+                'O' => CellState::WhiteStone,
+                '-' | '.' => CellState::BlankCell,
+                '#' => CellState::OuterCell,
+                _ => return Err(ReversiError::from(ParseErrorKind::InvalidCell(*c)).at(Location::Line(*line))),
+            };
+            has_stone |= matches!(cell_state, CellState::BlackStone | CellState::WhiteStone);
+            board.set_cell(row + 1, col + 1, cell_state)?;
+        }
+    }
+
+    if !has_stone {
+        put_center_stones(&mut board)?;
+    }
+    Ok(board)
+}
+
+/// 標準の初期配置と同じく、中央の4セルに石を置く
+fn put_center_stones(board: &mut Board) -> Result<(), ReversiError> {
+    let standard = Board::with_size(board.size())?;
+    let center = board.size() / 2;
+    for (row, col) in [(center, center), (center, center + 1), (center + 1, center), (center + 1, center + 1)] {
+        if board.get_cell(row, col) == Some(CellState::OuterCell) {
+            return Err(ReversiError::Blocked { row, col });
+        }
+        board.set_cell(row, col, standard.get_cell(row, col).unwrap())?;
+    }
+    Ok(())
+}
+
+/// レイアウトファイルを読み込む
+pub fn read_file(path: &str) -> Result<Board, ReversiError> {
+    let text = fs::read_to_string(path)?;
+    parse_layout(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OCTAGON: &str = "; 八角形\n##----##\n#------#\n--------\n\n---- ----\n--------\n--------\n#------# ; 7段目\n##----##\n";
+
+    fn layout_error(text: &str) -> (Location, ReversiError) {
+        match parse_layout(text) {
+            Err(ReversiError::At { location, source }) => (location, *source),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn octagon_gets_blocked_corners_and_the_centre_stones() {
+        let board = parse_layout(OCTAGON).unwrap();
+        assert_eq!(board.size(), 8);
+        assert_eq!(board.playable_cell_count(), 64 - 12);
+        for (row, col) in [(1, 1), (1, 2), (2, 1), (1, 8), (8, 7), (7, 8)] {
+            assert_eq!(board.get_cell(row, col), Some(CellState::OuterCell), "{} {}", row, col);
+        }
+        assert_eq!(board.get_cell(1, 3), Some(CellState::BlankCell));
+        let standard = Board::new();
+        for (row, col) in [(4, 4), (4, 5), (5, 4), (5, 5)] {
+            assert_eq!(board.get_cell(row, col), standard.get_cell(row, col));
+        }
+    }
+
+    #[test]
+    fn written_stones_replace_the_centre_stones() {
+        let board = parse_layout("#--#\n-XX-\n-XO-\n#--#").unwrap();
+        assert_eq!(board.get_cell(2, 2), Some(CellState::BlackStone));
+        assert_eq!(board.get_cell(2, 3), Some(CellState::BlackStone));
+        assert_eq!(board.get_cell(3, 3), Some(CellState::WhiteStone));
+    }
+
+    #[test]
+    fn rejects_an_unsupported_board_size() {
+        let square = |size: usize| format!("{}\n", "-".repeat(size)).repeat(size);
+        for (text, size) in [(square(5), 5), (square(2), 2), (square(18), 18), ("; comment\n".to_string(), 0)] {
+            assert!(matches!(parse_layout(&text), Err(ReversiError::InvalidBoardSize(s)) if s == size), "{}", size);
+        }
+    }
+
+    #[test]
+    fn reports_a_bad_row_at_its_line() {
+        let (location, source) = layout_error(&OCTAGON.replace("#------# ; 7段目", "#-----#"));
+        assert_eq!(location, Location::Line(9));
+        assert!(matches!(source, ReversiError::Parse(ParseErrorKind::InvalidLength { expected: 8, actual: 7 })));
+
+        let (location, source) = layout_error(&OCTAGON.replace("#------#\n--------", "#------#\n---*----"));
+        assert_eq!(location, Location::Line(4));
+        assert!(matches!(source, ReversiError::Parse(ParseErrorKind::InvalidCell('*'))));
+    }
+
+    #[test]
+    fn blocked_centre_needs_written_stones() {
+        assert!(matches!(parse_layout("----\n-#--\n----\n----"), Err(ReversiError::Blocked { row: 2, col: 2 })));
+    }
+}
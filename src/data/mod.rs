@@ -1 +1,8 @@
-pub mod fileio;
\ No newline at end of file
+pub mod fileio;
+pub mod move_list;
+pub mod ggf;
+pub mod wthor;
+pub mod position;
+pub mod layout;
+pub mod timestamp;
+pub mod config;
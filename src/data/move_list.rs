@@ -0,0 +1,114 @@
+use std::fs;
+
+use crate::domain_model::board::MAX_BOARD_SIZE;
+use crate::domain_model::cell_pos::CellPos;
+use crate::domain_model::r#move::Move;
+use crate::error::{Location, ParseErrorKind, ReversiError};
+
+// 標準的な棋譜表記。列をa～h、段を1～8で表し、"f5d6c3"のように連結する。パスは書かない
+// 8x8より大きい盤面では列をpまで、段を16まで使い、"j10a9"のように書く
+
+/// 手の並びを棋譜の文字列にする。パスは省く
+pub fn format_moves<'a>(moves: impl IntoIterator<Item = &'a Move>) -> String {
+    moves.into_iter()
+        .filter_map(|mv| mv.put_pos.as_ref())
+        .map(format_pos)
+        .collect()
+}
+
+/// セル位置を"f5"のような表記にする
+pub fn format_pos(pos: &CellPos) -> String {
+    format!("{}{}", (b'a' + (pos.col - 1) as u8) as char, pos.row)
+}
+
+/// 棋譜の文字列をセル位置の並びにする。大文字と空白も受け付ける
+pub fn parse_moves(text: &str) -> Result<Vec<CellPos>, ReversiError> {
+    let chars: Vec<char> = text.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+
+    // 1文字目の列に続く、2桁までの数字を段とする
+    let mut moves: Vec<CellPos> = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let mut end = start + 1;
+        while end < chars.len() && end - start <= 2 && chars[end].is_ascii_digit() {
+            end += 1;
+        }
+        let token: String = chars[start..end].iter().collect();
+        let pos = parse_pos(&token)
+            .ok_or_else(|| ReversiError::from(ParseErrorKind::InvalidMove(token))
+                .at(Location::Move(moves.len() + 1)))?;
+        moves.push(pos);
+        start = end;
+    }
+
+    Ok(moves)
+}
+
+/// "f5"のような表記をセル位置にする
+fn parse_pos(token: &str) -> Option<CellPos> {
+    let mut chars = token.chars();
+    let col = chars.next()?;
+    let row: usize = chars.as_str().parse().ok()?;
+    if !('a'..='p').contains(&col) || !(1..=MAX_BOARD_SIZE).contains(&row) {
+        return None;
+    }
+    Some(CellPos { row: row as i8, col: (col as u8 - b'a' + 1) as i8 })
+}
+
+/// 棋譜ファイルを読み込む
+pub fn read_file(path: &str) -> Result<Vec<CellPos>, ReversiError> {
+    let text = fs::read_to_string(path)?;
+    parse_moves(&text)
+}
+
+/// 棋譜ファイルに書き出す
+pub fn write_file<'a>(path: &str, moves: impl IntoIterator<Item = &'a Move>) -> Result<(), ReversiError> {
+    fs::write(path, format!("{}\n", format_moves(moves)))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_model::turn::Turn;
+
+    fn parsed(text: &str) -> Vec<(i8, i8)> {
+        parse_moves(text).unwrap().iter().map(|p| (p.row, p.col)).collect()
+    }
+
+    #[test]
+    fn parses_one_and_two_digit_rows() {
+        assert_eq!(parsed("f5d6c3"), vec![(5, 6), (6, 4), (3, 3)]);
+        assert_eq!(parsed("a10p16j9"), vec![(10, 1), (16, 16), (9, 10)]);
+        assert_eq!(parsed("F5 d6\nC3"), vec![(5, 6), (6, 4), (3, 3)]);
+        assert!(parsed("").is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_moves_with_their_number() {
+        for (text, number) in [("f5z1", 2), ("f5d17", 2), ("a0", 1), ("f5d", 2), ("a101", 2)] {
+            match parse_moves(text) {
+                Err(ReversiError::At { location, source }) => {
+                    assert_eq!(location, Location::Move(number), "{}", text);
+                    assert!(matches!(*source, ReversiError::Parse(ParseErrorKind::InvalidMove(_))), "{}", text);
+                },
+                other => panic!("{}: {:?}", text, other.map(|moves| moves.len())),
+            }
+        }
+    }
+
+    #[test]
+    fn formats_moves_without_passes() {
+        let mv = |put_pos: Option<CellPos>| Move { turn: Turn::Black, put_pos, flipped_pos_list: Vec::new(), eval: None, time: None };
+        let moves = vec![
+            mv(Some(CellPos { row: 5, col: 6 })),
+            mv(None),
+            mv(Some(CellPos { row: 10, col: 1 })),
+            mv(Some(CellPos { row: 16, col: 16 })),
+        ];
+        assert_eq!(format_moves(&moves), "f5a10p16");
+    }
+}
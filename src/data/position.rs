@@ -0,0 +1,125 @@
+use crate::domain_model::board::{Board, MIN_BOARD_SIZE, MAX_BOARD_SIZE};
+use crate::domain_model::cell::CellState;
+use crate::domain_model::turn::Turn;
+use crate::error::{Location, ParseErrorKind, ReversiError};
+
+// 局面の文字列表記(OBF形式)
+// 1段目の1列目から8段目の8列目まで、黒をX、白をO、空きを-で64文字並べ、空白の後に手番をXかOで書く
+// 例: ---------------------------OX------XO--------------------------- X
+// 8x8以外の盤面では、1辺のセル数の2乗の文字を並べる
+// 石を置けないセルは#で書く(この形式の独自の拡張)
+// 末尾の";"とそれに続くコメントは無視する。空きは"."も受け付ける
+
+/// 局面の文字列を盤面と手番にする
+pub fn parse_position(text: &str) -> Result<(Board, Turn), ReversiError> {
+    let text = match text.find(';') {
+        Some(end) => &text[..end],
+        None => text,
+    };
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+    // 文字数から盤面の大きさを決める。合わなければ、文字数が近い大きさの文字数を示す
+    let size = (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).step_by(2)
+        .find(|size| size * size + 1 >= chars.len())
+        .unwrap_or(MAX_BOARD_SIZE);
+    if chars.len() != size * size + 1 {
+        return Err(ParseErrorKind::InvalidLength { expected: size * size + 1, actual: chars.len() }.into());
+    }
+
+    let mut board = Board::with_size(size)?;
+    for (i, c) in chars[..size * size].iter().enumerate() {
+        let cell_state = match c.to_ascii_uppercase() {
+            'X' => CellState::BlackStone,
+            'O' => CellState::WhiteStone,
+            '-' | '.' => CellState::BlankCell,
+            '#' => CellState::OuterCell,
+            _ => return Err(ReversiError::from(ParseErrorKind::InvalidCell(*c))
+                .at(Location::Cell { row: i / size + 1, col: i % size + 1 })),
+        };
+        board.set_cell(i / size + 1, i % size + 1, cell_state)?;
+    }
+    let turn = match chars[size * size].to_ascii_uppercase() {
+        'X' => Turn::Black,
+        'O' => Turn::White,
+        c => return Err(ParseErrorKind::InvalidTurn(c.to_string()).into()),
+    };
+
+    Ok((board, turn))
+}
+
+/// 盤面と手番を局面の文字列にする
+pub fn format_position(board: &Board, turn: Turn) -> String {
+    let mut text = String::with_capacity(board.cell_count() + 2);
+    for row in 1..=board.size() {
+        for col in 1..=board.size() {
+            text.push(match board.get_cell(row, col) {
+                Some(CellState::BlackStone) => 'X',
+                Some(CellState::WhiteStone) => 'O',
+                Some(CellState::OuterCell) => '#',
+                _ => '-',
+            });
+        }
+    }
+    text.push(' ');
+    text.push(if turn == Turn::Black { 'X' } else { 'O' });
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: &str = "---------------------------OX------XO---------------------------";
+
+    #[test]
+    fn parses_an_8x8_position_with_either_side_to_move() {
+        assert_eq!(parse_position(&format!("{} X", START)).unwrap(), (Board::new(), Turn::Black));
+        assert_eq!(parse_position(&format!("{} O", START)).unwrap(), (Board::new(), Turn::White));
+
+        // 小文字、"."の空き、改行と末尾のコメントも受け付ける
+        let text = format!("{}\n{} o ; comment", &START[..32], START[32..].replace('-', ".").to_lowercase());
+        assert_eq!(parse_position(&text).unwrap(), (Board::new(), Turn::White));
+        assert_eq!(format_position(&Board::new(), Turn::White), format!("{} O", START));
+    }
+
+    #[test]
+    fn hash_marks_a_blocked_cell() {
+        let text = "#--# -OX- -XO- #--# X";
+        let (board, turn) = parse_position(text).unwrap();
+        assert_eq!((board.size(), turn), (4, Turn::Black));
+        for (row, col) in [(1, 1), (1, 4), (4, 1), (4, 4)] {
+            assert_eq!(board.get_cell(row, col), Some(CellState::OuterCell));
+        }
+        assert_eq!(board.get_cell(1, 2), Some(CellState::BlankCell));
+        assert_eq!(board.playable_cell_count(), 12);
+        assert_eq!(format_position(&board, turn), "#--#-OX--XO-#--# X");
+    }
+
+    #[test]
+    fn length_must_be_an_even_square_and_a_turn() {
+        // 手番がない、5x5、16x16より大きい
+        for (length, expected) in [(64, 65), (26, 37), (300, 257)] {
+            match parse_position(&"-".repeat(length)) {
+                Err(ReversiError::Parse(ParseErrorKind::InvalidLength { expected: e, actual })) => {
+                    assert_eq!((e, actual), (expected, length));
+                },
+                other => panic!("{}: {:?}", length, other),
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_an_invalid_side_to_move_and_cell() {
+        match parse_position(&format!("{} B", START)) {
+            Err(ReversiError::Parse(ParseErrorKind::InvalidTurn(turn))) => assert_eq!(turn, "B"),
+            other => panic!("{:?}", other),
+        }
+        match parse_position("-OX- -XO- ---* ---- X") {
+            Err(ReversiError::At { location, source }) => {
+                assert_eq!(location, Location::Cell { row: 3, col: 4 });
+                assert!(matches!(*source, ReversiError::Parse(ParseErrorKind::InvalidCell('*'))));
+            },
+            other => panic!("{:?}", other),
+        }
+    }
+}
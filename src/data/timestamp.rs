@@ -0,0 +1,47 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// UTCの日時
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timestamp {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl Timestamp {
+    /// 現在時刻
+    pub fn now() -> Timestamp {
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        Timestamp::from_unix_seconds(secs)
+    }
+
+    /// 1970年1月1日からの秒数を日時にする
+    pub fn from_unix_seconds(secs: u64) -> Timestamp {
+        let (year, month, day) = civil_from_days((secs / 86400) as i64);
+        let time = (secs % 86400) as u32;
+        Timestamp { year, month, day, hour: time / 3600, minute: time % 3600 / 60, second: time % 60 }
+    }
+
+    /// ISO 8601の形式(例: 2024-05-01T12:34:56Z)
+    pub fn format_iso8601(&self) -> String {
+        format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second)
+    }
+}
+
+/// 1970年1月1日からの日数を年月日にする
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
@@ -0,0 +1,351 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::domain_model::board::Board;
+use crate::domain_model::cell::{CellState, ICellState};
+use crate::domain_model::cell_pos::CellPos;
+use crate::domain_model::r#move::Move;
+use crate::domain_model::turn::Turn;
+use crate::error::{Location, ParseErrorKind, ReversiError};
+
+// WTHOR形式。フランスオセロ連盟が配布しているプロの棋譜のデータベース
+// どのファイルも16バイトのヘッダーの後に固定長のレコードが並ぶ
+//   .wtb: 棋譜。1レコード68バイト
+//   .jou: 対局者名。1レコード20バイト
+//   .trn: 大会名。1レコード26バイト
+
+const HEADER_SIZE: usize = 16;
+const GAME_RECORD_SIZE: usize = 68;
+const PLAYER_RECORD_SIZE: usize = 20;
+const TOURNAMENT_RECORD_SIZE: usize = 26;
+const MOVE_COUNT: usize = 60;
+
+// 対局者名と大会名のファイルの標準の名前(WTHOR.JOU, WTHOR.TRN)
+const STANDARD_STEM: &str = "WTHOR";
+
+/// WTHORファイルのヘッダー
+#[derive(Debug, Clone)]
+pub struct WthorHeader {
+    pub created: (u16, u8, u8), // 作成日(年, 月, 日)
+    pub game_count: u32,        // .wtbのレコード数
+    pub record_count: u16,      // .jou/.trnのレコード数
+    pub year: u16,              // 対局年
+    pub board_size: u8,         // 0または8なら8x8
+    pub theoretical_depth: u8,  // 理論スコアの読みの深さ
+}
+
+/// WTHORの1局
+#[derive(Debug, Clone)]
+pub struct WthorGame {
+    pub tournament: String,
+    pub black_player: String,
+    pub white_player: String,
+    pub black_score: u8,       // 実戦での終局時の黒石数
+    pub theoretical_score: u8, // 完全読みでの黒石数
+    pub moves: Vec<Move>,      // 置ける場所がないときのパスも含む
+}
+
+/// .wtbファイルの内容。壊れたレコードはエラーとして持つ
+#[derive(Debug)]
+pub struct WthorDatabase {
+    pub header: WthorHeader,
+    pub games: Vec<Result<WthorGame, ReversiError>>,
+}
+
+/// .wtbファイルを読み込む。対局者名と大会名はplayers、tournamentsから引く
+pub fn read_games(path: &str, players: &[String], tournaments: &[String]) -> Result<WthorDatabase, ReversiError> {
+    parse_database(&fs::read(path)?, players, tournaments)
+}
+
+/// .jouファイルから対局者名を読み込む
+pub fn read_players(path: &str) -> Result<Vec<String>, ReversiError> {
+    read_names(path, PLAYER_RECORD_SIZE)
+}
+
+/// .trnファイルから大会名を読み込む
+pub fn read_tournaments(path: &str) -> Result<Vec<String>, ReversiError> {
+    read_names(path, TOURNAMENT_RECORD_SIZE)
+}
+
+/// .wtbファイルと同じフォルダにある、拡張子がextensionのファイルを探す。大文字小文字は区別しない。
+/// 複数あれば、.wtbファイルと同じ名前のもの、標準の名前WTHORのもの、名前順で最初のものの順に選ぶ
+pub fn find_companion_file(wtb_path: &str, extension: &str) -> Option<String> {
+    let wtb_path = Path::new(wtb_path);
+    let dir = match wtb_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir).ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension()
+            .map(|e| e.to_string_lossy().eq_ignore_ascii_case(extension))
+            .unwrap_or(false))
+        .collect();
+    paths.sort();
+
+    let has_stem = |path: &PathBuf, stem: &str| path.file_stem()
+        .map(|s| s.to_string_lossy().eq_ignore_ascii_case(stem))
+        .unwrap_or(false);
+    let wtb_stem = wtb_path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let found = paths.iter().find(|path| has_stem(path, &wtb_stem))
+        .or_else(|| paths.iter().find(|path| has_stem(path, STANDARD_STEM)))
+        .or_else(|| paths.first())?;
+    Some(found.to_string_lossy().to_string())
+}
+
+fn parse_database(bytes: &[u8], players: &[String], tournaments: &[String]) -> Result<WthorDatabase, ReversiError> {
+    let header = parse_header(bytes)?;
+    if header.board_size != 0 && header.board_size != 8 {
+        return Err(ParseErrorKind::UnsupportedBoardSize(header.board_size.to_string()).into());
+    }
+
+    let records = records(bytes, GAME_RECORD_SIZE, header.game_count as usize)?;
+    let games = records.iter()
+        .enumerate()
+        .map(|(i, record)| parse_game(record, players, tournaments)
+            .map_err(|e| e.at(Location::Game(i + 1))))
+        .collect();
+
+    Ok(WthorDatabase { header, games })
+}
+
+fn read_names(path: &str, record_size: usize) -> Result<Vec<String>, ReversiError> {
+    parse_names(&fs::read(path)?, record_size)
+}
+
+fn parse_names(bytes: &[u8], record_size: usize) -> Result<Vec<String>, ReversiError> {
+    let header = parse_header(bytes)?;
+    let names = records(bytes, record_size, header.record_count as usize)?
+        .iter()
+        .map(|record| {
+            // 名前はNUL終端。文字コードはLatin-1
+            let end = record.iter().position(|&b| b == 0).unwrap_or(record.len());
+            record[..end].iter().map(|&b| b as char).collect::<String>().trim().to_string()
+        })
+        .collect();
+    Ok(names)
+}
+
+fn parse_header(bytes: &[u8]) -> Result<WthorHeader, ReversiError> {
+    if bytes.len() < HEADER_SIZE {
+        return Err(ParseErrorKind::Truncated.into());
+    }
+
+    Ok(WthorHeader {
+        created: (bytes[0] as u16 * 100 + bytes[1] as u16, bytes[2], bytes[3]),
+        game_count: u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+        record_count: u16::from_le_bytes([bytes[8], bytes[9]]),
+        year: u16::from_le_bytes([bytes[10], bytes[11]]),
+        board_size: bytes[12],
+        theoretical_depth: bytes[14],
+    })
+}
+
+/// ヘッダーの後ろのレコードを切り出す。ヘッダーのレコード数より短ければエラー
+fn records(bytes: &[u8], record_size: usize, count: usize) -> Result<Vec<&[u8]>, ReversiError> {
+    let body = &bytes[HEADER_SIZE..];
+    if body.len() / record_size < count {
+        return Err(ParseErrorKind::MissingRecords { expected: count, actual: body.len() / record_size }.into());
+    }
+    Ok(body.chunks_exact(record_size).take(count).collect())
+}
+
+fn parse_game(record: &[u8], players: &[String], tournaments: &[String]) -> Result<WthorGame, ReversiError> {
+    let name = |names: &[String], id: u16| names.get(id as usize).cloned().unwrap_or_else(|| format!("#{}", id));
+    let tournament_id = u16::from_le_bytes([record[0], record[1]]);
+    let black_id = u16::from_le_bytes([record[2], record[3]]);
+    let white_id = u16::from_le_bytes([record[4], record[5]]);
+
+    Ok(WthorGame {
+        tournament: name(tournaments, tournament_id),
+        black_player: name(players, black_id),
+        white_player: name(players, white_id),
+        black_score: record[6],
+        theoretical_score: record[7],
+        moves: parse_moves(&record[8..8 + MOVE_COUNT])?,
+    })
+}
+
+/// 1手を「段 * 10 + 列」の1バイトで表した手順を、初期局面から打ちながら手の並びにする。
+/// 0が出たらそこで終わり。パスは書かれていないので、置ける場所がなければ補う
+fn parse_moves(bytes: &[u8]) -> Result<Vec<Move>, ReversiError> {
+    let mut board = Board::new();
+    let mut turn = Turn::Black;
+    let mut moves: Vec<Move> = Vec::new();
+
+    for (i, &byte) in bytes.iter().enumerate() {
+        if byte == 0 {
+            break;
+        }
+        let (row, col) = ((byte / 10) as usize, (byte % 10) as usize);
+        if !board.is_in_range(row, col) {
+            return Err(ReversiError::from(ParseErrorKind::InvalidNumber(byte.to_string())).at(Location::Move(i + 1)));
+        }
+
+        let mut stone = turn_to_stone(turn);
+        if !board.has_legal_move(stone)? {
+            moves.push(Move { turn, put_pos: None, flipped_pos_list: Vec::new(), eval: None, time: None });
+            turn = opposite_turn(turn);
+            stone = stone.get_reverse_stone();
+        }
+
+        let flippable_cells = board.find_flippable_cells(row, col, stone)?;
+        if board.get_cell(row, col) != Some(CellState::BlankCell) {
+            return Err(ReversiError::Occupied { row, col }.at(Location::Move(i + 1)));
+        }
+        if flippable_cells.is_empty() {
+            return Err(ReversiError::NoFlips { row, col }.at(Location::Move(i + 1)));
+        }
+        board.set_cell(row, col, stone)?;
+        board.flip(&flippable_cells);
+        moves.push(Move {
+            turn,
+            put_pos: Some(CellPos { row: row as i8, col: col as i8 }),
+            flipped_pos_list: flippable_cells,
+            eval: None,
+            time: None,
+        });
+        turn = opposite_turn(turn);
+    }
+
+    Ok(moves)
+}
+
+fn turn_to_stone(turn: Turn) -> CellState {
+    match turn {
+        Turn::Black => CellState::BlackStone,
+        Turn::White => CellState::WhiteStone,
+    }
+}
+
+fn opposite_turn(turn: Turn) -> Turn {
+    if turn == Turn::Black { Turn::White } else { Turn::Black }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(game_count: u32, record_count: u16, board_size: u8) -> Vec<u8> {
+        let mut bytes = vec![20, 24, 10, 18];
+        bytes.extend_from_slice(&game_count.to_le_bytes());
+        bytes.extend_from_slice(&record_count.to_le_bytes());
+        bytes.extend_from_slice(&2024u16.to_le_bytes());
+        bytes.extend_from_slice(&[board_size, 0, 22, 0]);
+        bytes
+    }
+
+    fn game_record(ids: [u16; 3], scores: [u8; 2], moves: &[u8]) -> Vec<u8> {
+        let mut bytes: Vec<u8> = ids.iter().flat_map(|id| id.to_le_bytes()).collect();
+        bytes.extend_from_slice(&scores);
+        bytes.extend_from_slice(moves);
+        bytes.resize(GAME_RECORD_SIZE, 0);
+        bytes
+    }
+
+    fn name_record(name: &[u8]) -> Vec<u8> {
+        let mut bytes = name.to_vec();
+        bytes.resize(PLAYER_RECORD_SIZE, 0);
+        bytes
+    }
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn reads_player_names_as_latin1() {
+        let mut bytes = header(0, 2, 0);
+        bytes.extend(name_record(b"Tamenori Hideshi"));
+        bytes.extend(name_record(b"L\xe9vy  "));
+        bytes.extend(name_record(b"not counted"));
+        assert_eq!(parse_names(&bytes, PLAYER_RECORD_SIZE).unwrap(), names(&["Tamenori Hideshi", "Lévy"]));
+    }
+
+    #[test]
+    fn reads_games_from_a_wtb_buffer() {
+        let mut bytes = header(2, 0, 8);
+        // f5 d6 c3 d3 c4
+        bytes.extend(game_record([1, 0, 1], [40, 36], &[56, 64, 33, 34, 43]));
+        // d4には最初から石がある
+        bytes.extend(game_record([0, 2, 0], [0, 0], &[56, 44]));
+        let database = parse_database(&bytes, &names(&["Black", "White"]), &names(&["", "Open"])).unwrap();
+
+        let header = &database.header;
+        assert_eq!((header.created, header.game_count, header.year), ((2024, 10, 18), 2, 2024));
+        assert_eq!((header.board_size, header.theoretical_depth), (8, 22));
+        assert_eq!(database.games.len(), 2);
+
+        let game = database.games[0].as_ref().unwrap();
+        assert_eq!(game.tournament, "Open");
+        assert_eq!((game.black_player.as_str(), game.white_player.as_str()), ("Black", "White"));
+        assert_eq!((game.black_score, game.theoretical_score), (40, 36));
+        let moves: Vec<(Turn, Option<(i8, i8)>)> = game.moves.iter()
+            .map(|mv| (mv.turn, mv.put_pos.as_ref().map(|pos| (pos.row, pos.col))))
+            .collect();
+        assert_eq!(moves, vec![
+            (Turn::Black, Some((5, 6))),
+            (Turn::White, Some((6, 4))),
+            (Turn::Black, Some((3, 3))),
+            (Turn::White, Some((3, 4))),
+            (Turn::Black, Some((4, 3))),
+        ]);
+
+        match &database.games[1] {
+            Err(ReversiError::At { location: Location::Game(2), source }) => match source.as_ref() {
+                ReversiError::At { location, source } => {
+                    assert_eq!(*location, Location::Move(2));
+                    assert!(matches!(source.as_ref(), ReversiError::Occupied { row: 4, col: 4 }));
+                },
+                other => panic!("{:?}", other),
+            },
+            other => panic!("{:?}", other.as_ref().map(|game| game.moves.len())),
+        }
+    }
+
+    #[test]
+    fn unknown_player_ids_are_shown_as_numbers() {
+        let mut bytes = header(1, 0, 0);
+        bytes.extend(game_record([3, 7, 0], [32, 32], &[]));
+        let database = parse_database(&bytes, &names(&["Black"]), &[]).unwrap();
+        let game = database.games[0].as_ref().unwrap();
+        assert_eq!((game.tournament.as_str(), game.black_player.as_str(), game.white_player.as_str()), ("#3", "#7", "Black"));
+        assert!(game.moves.is_empty());
+    }
+
+    #[test]
+    fn rejects_broken_buffers() {
+        let mut bytes = header(3, 0, 8);
+        bytes.extend(game_record([0, 0, 0], [0, 0], &[56]));
+        let error = parse_database(&bytes, &[], &[]).unwrap_err();
+        assert!(matches!(error.root(), ReversiError::Parse(ParseErrorKind::MissingRecords { expected: 3, actual: 1 })));
+
+        let error = parse_database(&header(0, 0, 10), &[], &[]).unwrap_err();
+        assert!(matches!(error.root(), ReversiError::Parse(ParseErrorKind::UnsupportedBoardSize(size)) if size == "10"));
+
+        let error = parse_names(&header(0, 0, 0)[..10], PLAYER_RECORD_SIZE).unwrap_err();
+        assert!(matches!(error.root(), ReversiError::Parse(ParseErrorKind::Truncated)));
+    }
+
+    #[test]
+    fn companion_file_prefers_the_same_name_then_the_standard_name() {
+        let dir = std::env::temp_dir().join(format!("rust_reversi_wthor_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let find = |wtb: &str| find_companion_file(dir.join(wtb).to_str().unwrap(), "jou")
+            .map(|path| Path::new(&path).file_name().unwrap().to_string_lossy().to_string());
+
+        // 名前順では"A.JOU"が最初になる
+        for name in ["A.JOU", "WTHOR.JOU", "wth_2001.jou", "wth_2001.trn"] {
+            fs::write(dir.join(name), b"").unwrap();
+        }
+        assert_eq!(find("WTH_2001.WTB").as_deref(), Some("wth_2001.jou"));
+        assert_eq!(find("WTH_2002.wtb").as_deref(), Some("WTHOR.JOU"));
+
+        fs::remove_file(dir.join("WTHOR.JOU")).unwrap();
+        assert_eq!(find("WTH_2002.wtb").as_deref(), Some("A.JOU"));
+        assert_eq!(find_companion_file(dir.join("WTH_2002.wtb").to_str().unwrap(), "pgn"), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
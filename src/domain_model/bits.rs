@@ -0,0 +1,198 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+/// 256ビットのビットボード。16x16までの盤面のセルを1ビットずつ表す。
+/// ビット番号indexは0から255で、[u64; 4]の下位の要素から順に並べる
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Bits([u64; 4]);
+
+impl Bits {
+    /// どのビットも立っていない
+    pub const EMPTY: Bits = Bits([0; 4]);
+
+    /// indexのビットだけが立っている
+    pub const fn bit(index: u32) -> Bits {
+        Bits::EMPTY.with(index)
+    }
+
+    /// 下位64ビットがwordで、それより上のビットが立っていない
+    pub const fn from_low(word: u64) -> Bits {
+        Bits([word, 0, 0, 0])
+    }
+
+    /// 下位64ビット
+    pub fn low(&self) -> u64 {
+        self.0[0]
+    }
+
+    /// indexのビットを立てたもの
+    pub const fn with(self, index: u32) -> Bits {
+        let mut words = self.0;
+        words[(index / 64) as usize] |= 1u64 << (index % 64);
+        Bits(words)
+    }
+
+    /// notのビットを落としたもの
+    pub const fn without(self, not: Bits) -> Bits {
+        Bits([self.0[0] & !not.0[0], self.0[1] & !not.0[1], self.0[2] & !not.0[2], self.0[3] & !not.0[3]])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (self.0[0] | self.0[1] | self.0[2] | self.0[3]) == 0
+    }
+
+    pub fn contains(&self, index: u32) -> bool {
+        self.0[(index / 64) as usize] & (1u64 << (index % 64)) != 0
+    }
+
+    pub fn count_ones(&self) -> u32 {
+        self.0.iter().map(|word| word.count_ones()).sum()
+    }
+
+    /// 最下位の立っているビットの番号。どのビットも立っていなければ256
+    pub fn trailing_zeros(&self) -> u32 {
+        for (i, word) in self.0.iter().enumerate() {
+            if *word != 0 {
+                return i as u32 * 64 + word.trailing_zeros();
+            }
+        }
+        256
+    }
+
+    /// ビット番号が大きくなる方へamountだけずらす。負なら小さくなる方へずらす。amountの絶対値は64未満
+    pub fn shift(self, amount: i32) -> Bits {
+        let w = self.0;
+        if amount > 0 {
+            let n = amount as u32;
+            Bits([
+                w[0] << n,
+                w[1] << n | w[0] >> (64 - n),
+                w[2] << n | w[1] >> (64 - n),
+                w[3] << n | w[2] >> (64 - n),
+            ])
+        } else if amount < 0 {
+            let n = (-amount) as u32;
+            Bits([
+                w[0] >> n | w[1] << (64 - n),
+                w[1] >> n | w[2] << (64 - n),
+                w[2] >> n | w[3] << (64 - n),
+                w[3] >> n,
+            ])
+        } else {
+            self
+        }
+    }
+
+    /// 立っているビットの番号を小さい順に返す
+    pub fn iter(self) -> BitsIter {
+        BitsIter(self)
+    }
+}
+
+/// Bits::iterで立っているビットの番号を順に返す
+pub struct BitsIter(Bits);
+
+impl Iterator for BitsIter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        for (i, word) in self.0 .0.iter_mut().enumerate() {
+            if *word != 0 {
+                let index = i as u32 * 64 + word.trailing_zeros();
+                *word &= *word - 1;
+                return Some(index);
+            }
+        }
+        None
+    }
+}
+
+impl BitAnd for Bits {
+    type Output = Bits;
+    fn bitand(self, rhs: Bits) -> Bits {
+        Bits([self.0[0] & rhs.0[0], self.0[1] & rhs.0[1], self.0[2] & rhs.0[2], self.0[3] & rhs.0[3]])
+    }
+}
+
+impl BitOr for Bits {
+    type Output = Bits;
+    fn bitor(self, rhs: Bits) -> Bits {
+        Bits([self.0[0] | rhs.0[0], self.0[1] | rhs.0[1], self.0[2] | rhs.0[2], self.0[3] | rhs.0[3]])
+    }
+}
+
+impl BitXor for Bits {
+    type Output = Bits;
+    fn bitxor(self, rhs: Bits) -> Bits {
+        Bits([self.0[0] ^ rhs.0[0], self.0[1] ^ rhs.0[1], self.0[2] ^ rhs.0[2], self.0[3] ^ rhs.0[3]])
+    }
+}
+
+impl Not for Bits {
+    type Output = Bits;
+    fn not(self) -> Bits {
+        Bits([!self.0[0], !self.0[1], !self.0[2], !self.0[3]])
+    }
+}
+
+impl BitAndAssign for Bits {
+    fn bitand_assign(&mut self, rhs: Bits) {
+        *self = *self & rhs;
+    }
+}
+
+impl BitOrAssign for Bits {
+    fn bitor_assign(&mut self, rhs: Bits) {
+        *self = *self | rhs;
+    }
+}
+
+impl BitXorAssign for Bits {
+    fn bitxor_assign(&mut self, rhs: Bits) {
+        *self = *self ^ rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shift_carries_across_every_word_boundary() {
+        for boundary in [64, 128, 192] {
+            assert_eq!(Bits::bit(boundary - 1).shift(1), Bits::bit(boundary), "boundary {}", boundary);
+            assert_eq!(Bits::bit(boundary - 3).shift(17), Bits::bit(boundary + 14), "boundary {}", boundary);
+            assert_eq!(Bits::bit(boundary).shift(-1), Bits::bit(boundary - 1), "boundary {}", boundary);
+            assert_eq!(Bits::bit(boundary + 5).shift(-63), Bits::bit(boundary - 58), "boundary {}", boundary);
+        }
+    }
+
+    #[test]
+    fn shift_drops_bits_beyond_both_ends() {
+        assert_eq!(Bits::bit(250).shift(10), Bits::EMPTY);
+        assert_eq!(Bits::bit(5).shift(-10), Bits::EMPTY);
+        assert_eq!(Bits::bit(100).shift(0), Bits::bit(100));
+    }
+
+    #[test]
+    fn iter_returns_indexes_in_ascending_order() {
+        let bits = Bits::bit(200) | Bits::bit(0) | Bits::bit(64) | Bits::bit(63);
+        assert_eq!(bits.iter().collect::<Vec<_>>(), vec![0, 63, 64, 200]);
+        assert_eq!(bits.count_ones(), 4);
+        assert_eq!(Bits::EMPTY.iter().next(), None);
+    }
+
+    #[test]
+    fn trailing_zeros_spans_all_words() {
+        assert_eq!(Bits::bit(0).trailing_zeros(), 0);
+        assert_eq!((Bits::bit(191) | Bits::bit(255)).trailing_zeros(), 191);
+        assert_eq!(Bits::EMPTY.trailing_zeros(), 256);
+    }
+
+    #[test]
+    fn without_clears_only_given_bits() {
+        let bits = Bits::bit(1) | Bits::bit(70) | Bits::bit(140);
+        assert_eq!(bits.without(Bits::bit(70)), Bits::bit(1) | Bits::bit(140));
+        assert!(bits.contains(140));
+        assert!(!bits.without(bits).contains(140));
+    }
+}
@@ -1,152 +1,566 @@
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+
+use crate::domain_model::bits::Bits;
 use crate::domain_model::cell::{CellState, ICellState};
 use crate::error::ReversiError;
 use crate::domain_model::cell_pos::CellPos;
+use crate::domain_model::legal_move::LegalMove;
+
+/// 盤面の1辺のセル数の最小と最大。この間の偶数にできる
+pub const MIN_BOARD_SIZE: usize = 4;
+pub const MAX_BOARD_SIZE: usize = 16;
+
+/// 標準の盤面の1辺のセル数
+pub const DEFAULT_BOARD_SIZE: usize = 8;
+
+// ビットボードのビット番号は (row - 1) * size + (col - 1)
+
+/// 盤面の大きさごとのマスクと探索方向
+struct Geometry {
+    cells: Bits,   // 盤面内のセル
+    corners: Bits, // 4隅のセル
+    // 探索方向ごとのシフト量とシフト後のマスク。正のシフト量はビット番号が大きくなる方
+    // 右, 右下, 下, 左下, 左, 左上, 上, 右上
+    directions: [(i32, Bits); 8],
+}
+
+const fn geometry(size: usize) -> Geometry {
+    let mut cells = Bits::EMPTY;
+    let mut first_col = Bits::EMPTY;
+    let mut last_col = Bits::EMPTY;
+    let mut row = 0;
+    while row < size {
+        let mut col = 0;
+        while col < size {
+            cells = cells.with((row * size + col) as u32);
+            col += 1;
+        }
+        first_col = first_col.with((row * size) as u32);
+        last_col = last_col.with((row * size + size - 1) as u32);
+        row += 1;
+    }
+    let last = (size * size - 1) as u32;
+    let corners = Bits::bit(0).with(size as u32 - 1).with(last + 1 - size as u32).with(last);
+
+    // 左右へのシフトで盤面の端を跨いだ先の列と、盤面の外に出たビットを落とすマスク
+    let not_first_col = cells.without(first_col);
+    let not_last_col = cells.without(last_col);
+    let s = size as i32;
+    Geometry {
+        cells,
+        corners,
+        directions: [
+            (1, not_first_col),
+            (s + 1, not_first_col),
+            (s, cells),
+            (s - 1, not_last_col),
+            (-1, not_last_col),
+            (-s - 1, not_last_col),
+            (-s, cells),
+            (-s + 1, not_first_col),
+        ],
+    }
+}
+
+// 1辺が4, 6, ..., 16の盤面
+static GEOMETRIES: [Geometry; 7] = [
+    geometry(4), geometry(6), geometry(8), geometry(10), geometry(12), geometry(14), geometry(16),
+];
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Board {
-    pub cells: [[CellState; 10]; 10],
-    search_directions: [CellPos; 8],
+    size: usize,
+    black: Bits,
+    white: Bits,
+    blocked: Bits, // 盤面内の置けないセル。石を置けず、石を挟むこともできない
 }
 
 impl Board {
+    /// 8x8の標準の初期配置
     pub fn new() -> Board {
-        
-        let serch_dir = [
-            CellPos{row: 0, col: 1},
-            CellPos{row: 1, col: 1},
-            CellPos{row: 1, col: 0},
-            CellPos{row: 1, col: -1},
-            CellPos{row: 0, col: -1},
-            CellPos{row: -1, col: -1},
-            CellPos{row: -1, col: 0},
-            CellPos{row: -1, col: 1},
-        ];
-
-        // cell初期化
-        fn make_cells() -> [[CellState; 10]; 10] {
-            let mut cells = [[CellState::OuterCell; 10]; 10];
-            for i in 1..=8 {
-                for j in 1..=8 {
-                    cells[i][j] = CellState::BlankCell;
-                }
-            }
-            cells[4][4] = CellState::WhiteStone;
-            cells[4][5] = CellState::BlackStone;
-            cells[5][4] = CellState::BlackStone;
-            cells[5][5] = CellState::WhiteStone;
-            cells
-        }
+        Board::standard(DEFAULT_BOARD_SIZE)
+    }
 
-        Board {
-            cells: make_cells(),
-            search_directions: serch_dir,
+    /// 1辺がsizeセルの盤面の、中央に4つ石を置いた初期配置
+    pub fn with_size(size: usize) -> Result<Board, ReversiError> {
+        if !Board::is_valid_size(size) {
+            return Err(ReversiError::InvalidBoardSize(size));
         }
+        Ok(Board::standard(size))
+    }
+
+    /// 盤面の1辺のセル数として使えるか
+    pub fn is_valid_size(size: usize) -> bool {
+        (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&size) && size % 2 == 0
+    }
+
+    fn standard(size: usize) -> Board {
+        let mut board = Board { size, black: Bits::EMPTY, white: Bits::EMPTY, blocked: Bits::EMPTY };
+        let center = size / 2;
+        board.black |= board.pos_to_bit(center, center + 1) | board.pos_to_bit(center + 1, center);
+        board.white |= board.pos_to_bit(center, center) | board.pos_to_bit(center + 1, center + 1);
+        board
+    }
+
+    /// 盤面の1辺のセル数
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// 盤面のセル数
+    pub fn cell_count(&self) -> usize {
+        self.size * self.size
+    }
+
+    /// 置けないセルを除いた、石を置けるセルの数
+    pub fn playable_cell_count(&self) -> usize {
+        self.cell_count() - self.blocked.count_ones() as usize
+    }
+
+    fn geometry(&self) -> &'static Geometry {
+        &GEOMETRIES[self.size / 2 - 2]
     }
 
     /// 盤面の範囲内か
     pub fn is_in_range(&self, row: usize, col: usize) -> bool {
-        !(row < 1 || 8 < row || col < 1 || 8 < col)
+        (1..=self.size).contains(&row) && (1..=self.size).contains(&col)
     }
 
     pub fn is_in_range_with_outercell(&self, row: usize, col: usize) -> bool {
-        !(row < 0 || 9 < row || col < 0 || 9 < col)
+        row <= self.size + 1 && col <= self.size + 1
+    }
+
+    /// セル位置をビットボードのビット番号に変換する。範囲内であること
+    pub fn pos_to_index(&self, row: usize, col: usize) -> u32 {
+        ((row - 1) * self.size + (col - 1)) as u32
     }
 
+    /// セル位置をビットボードのビットに変換する。範囲内であること
+    pub fn pos_to_bit(&self, row: usize, col: usize) -> Bits {
+        Bits::bit(self.pos_to_index(row, col))
+    }
+
+    /// ビット番号をセル位置に変換する
+    pub fn index_to_pos(&self, index: u32) -> CellPos {
+        let size = self.size as u32;
+        CellPos { row: (index / size + 1) as i8, col: (index % size + 1) as i8 }
+    }
+
+    /// セルの状態。盤面の外側と盤面内の置けないセルはOuterCell
     pub fn get_cell(&self, row: usize, col: usize) -> Option<CellState> {
-        if self.is_in_range_with_outercell(row, col) {
-            Some(self.cells[row][col])
+        if self.is_in_range(row, col) {
+            let index = self.pos_to_index(row, col);
+            if self.blocked.contains(index) {
+                Some(CellState::OuterCell)
+            } else if self.black.contains(index) {
+                Some(CellState::BlackStone)
+            } else if self.white.contains(index) {
+                Some(CellState::WhiteStone)
+            } else {
+                Some(CellState::BlankCell)
+            }
+        } else if self.is_in_range_with_outercell(row, col) {
+            Some(CellState::OuterCell)
         } else {
             None
         }
     }
 
+    /// セルの状態を変える。OuterCellを指定すると、そのセルを置けないセルにする
     pub fn set_cell(&mut self, row: usize, col: usize, cell_state: CellState) -> Result<(), ReversiError> {
-        if self.is_in_range(row, col) {
-            self.cells[row][col] = cell_state;
-            Ok(())
-        } else {
-            Err(ReversiError::new(format!("セルインデックスが範囲外です: row={}, col={}", row, col)))
+        if !self.is_in_range(row, col) {
+            return Err(ReversiError::OutOfRange { row, col });
         }
+
+        let bit = self.pos_to_bit(row, col);
+        self.black = self.black.without(bit);
+        self.white = self.white.without(bit);
+        self.blocked = self.blocked.without(bit);
+        match cell_state {
+            CellState::BlackStone => self.black |= bit,
+            CellState::WhiteStone => self.white |= bit,
+            CellState::BlankCell => {},
+            CellState::OuterCell => self.blocked |= bit,
+        }
+        Ok(())
+    }
+
+    /// 盤面内に置けないセルがあるか
+    pub fn has_blocked_cell(&self) -> bool {
+        !self.blocked.is_empty()
     }
 
     pub fn has_blank_cell(&self) -> bool {
-        for row in 1..=8 {
-            for col in 1..=8 {
-                if self.cells[row][col] == CellState::BlankCell {
-                    return true
-                }
+        !self.blank_bits().is_empty()
+    }
+
+    pub fn count_black_stones(&self) -> i16 {
+        self.black.count_ones() as i16
+    }
+
+    pub fn count_white_stones(&self) -> i16 {
+        self.white.count_ones() as i16
+    }
+
+    pub fn count_blank_cells(&self) -> i16 {
+        self.blank_bits().count_ones() as i16
+    }
+
+    pub fn flip(&mut self, cell_pos_list: &[CellPos]) {
+        for pos in cell_pos_list.iter() {
+            let bit = self.pos_to_bit(pos.row as usize, pos.col as usize);
+            if !((self.black | self.white) & bit).is_empty() {
+                self.black ^= bit;
+                self.white ^= bit;
             }
         }
+    }
+
+    // test: CellStateにBlankCell, OuterCellを指定したらエラー
+    pub fn find_flippable_cells(&self, row: usize, col: usize, stone: CellState) -> Result<Vec<CellPos>, ReversiError> {
+        match stone {
+            CellState::BlankCell | CellState::OuterCell => return Err(ReversiError::NotAStone(stone)),
+            _ => {},
+        }
+        if !self.is_in_range(row, col) {
+            return Ok(Vec::new());
+        }
 
-        false
+        let index = self.pos_to_index(row, col);
+        Ok(self.bits_to_cells(self.flip_bits(index, stone)))
     }
 
-    pub fn count_black_stones(&self) -> i8 {
-        let mut black_stone_count: i8 = 0;
-        for row in 1..=8 {
-            for col in 1..=8 {
-                if self.cells[row][col] == CellState::BlackStone {
-                    black_stone_count += 1;
-                }
-            }
+    /// stoneを置ける全てのセルと、それぞれのセルで反転できる石を返す
+    pub fn legal_moves(&self, stone: CellState) -> Result<Vec<LegalMove>, ReversiError> {
+        match stone {
+            CellState::BlankCell | CellState::OuterCell => return Err(ReversiError::NotAStone(stone)),
+            _ => {},
         }
 
-        black_stone_count
+        Ok(self.legal_moves_bits(stone).iter()
+            .map(|index| LegalMove {
+                put_pos: self.index_to_pos(index),
+                flippable_cells: self.bits_to_cells(self.flip_bits(index, stone)),
+            })
+            .collect())
     }
 
-    pub fn count_white_stones(&self) -> i8 {
-        64 - self.count_black_stones()
+    /// stoneを置けるセルが1つでもあるか
+    pub fn has_legal_move(&self, stone: CellState) -> Result<bool, ReversiError> {
+        match stone {
+            CellState::BlankCell | CellState::OuterCell => Err(ReversiError::NotAStone(stone)),
+            _ => Ok(!self.legal_moves_bits(stone).is_empty()),
+        }
     }
 
-    pub fn flip(&mut self, cell_pos_list: &Vec<CellPos>) {
-        for pos in cell_pos_list.iter() {
-            self.cells[pos.row as usize][pos.col as usize] = self.cells[pos.row as usize][pos.col as usize].get_reverse_stone();
+    /// stoneの石があるセルのビットボード。石以外を指定したら空
+    pub fn stone_bits(&self, stone: CellState) -> Bits {
+        match stone {
+            CellState::BlackStone => self.black,
+            CellState::WhiteStone => self.white,
+            _ => Bits::EMPTY,
         }
     }
 
-    // test: CellStateにBlankCell, OuterCellを指定したらエラー
-    pub fn find_flippable_cells(&self, row: usize, col: usize, stone: CellState) -> Result<Vec<CellPos>, ReversiError> {
+    /// 空きセルのビットボード。置けないセルは含まない
+    pub fn blank_bits(&self) -> Bits {
+        self.geometry().cells.without(self.black | self.white | self.blocked)
+    }
+
+    /// 4隅のセルのビットボード。置けないセルは含まない
+    pub fn corner_bits(&self) -> Bits {
+        self.geometry().corners.without(self.blocked)
+    }
+
+    /// stoneを置けるセルのビットボード
+    pub fn legal_moves_bits(&self, stone: CellState) -> Bits {
+        let own = self.stone_bits(stone);
+        let opponent = self.stone_bits(stone.get_reverse_stone());
+        let blank = self.blank_bits();
+        let directions = &self.geometry().directions;
+
+        // 8x8以下の盤面は下位64ビットに収まるので、u64のまま計算して速くする。
+        // 標準の8x8はシフト量を定数にできるよう分ける
+        match self.size {
+            DEFAULT_BOARD_SIZE => Bits::from_low(legal_moves(own.low(), opponent.low(), blank.low(),
+                &GEOMETRIES[DEFAULT_BOARD_SIZE / 2 - 2].directions, DEFAULT_BOARD_SIZE)),
+            size if size < DEFAULT_BOARD_SIZE =>
+                Bits::from_low(legal_moves(own.low(), opponent.low(), blank.low(), directions, size)),
+            size => legal_moves(own, opponent, blank, directions, size),
+        }
+    }
+
+    /// ビット番号indexにstoneを置いたときに反転する石のビットボード。
+    /// indexのセルが空いているかは確認しない
+    pub fn flip_bits(&self, index: u32, stone: CellState) -> Bits {
+        let own = self.stone_bits(stone);
+        let opponent = self.stone_bits(stone.get_reverse_stone());
+        let put = Bits::bit(index);
+        let directions = &self.geometry().directions;
+
+        match self.size {
+            DEFAULT_BOARD_SIZE => Bits::from_low(flips(put.low(), own.low(), opponent.low(),
+                &GEOMETRIES[DEFAULT_BOARD_SIZE / 2 - 2].directions)),
+            size if size < DEFAULT_BOARD_SIZE =>
+                Bits::from_low(flips(put.low(), own.low(), opponent.low(), directions)),
+            _ => flips(put, own, opponent, directions),
+        }
+    }
+
+    /// ビット番号indexにstoneを置き、flipsの石を反転する
+    pub fn put_bits(&mut self, index: u32, flips: Bits, stone: CellState) {
+        let put = Bits::bit(index);
         match stone {
-            CellState::BlankCell => return Err(ReversiError::new("BlankCellは指定できません")), 
-            CellState::OuterCell => return Err(ReversiError::new("OuterCellは指定できません")),
+            CellState::BlackStone => {
+                self.black |= put | flips;
+                self.white = self.white.without(flips);
+            },
+            CellState::WhiteStone => {
+                self.white |= put | flips;
+                self.black = self.black.without(flips);
+            },
             _ => {},
         }
-        let row = row as i8;
-        let col = col as i8;
-
-		// 反転できるか探索する
-		// 全8方向について、異なる色の石がある間、この石をカウントしながら進み、次の条件
-		// ・盤の範囲外となる(OuterCell)
-		// ・空のマスとなる(BlankCell)
-		// ・同じ色の石となる(DarkDisk|LightDisk)
-		// に合致すればループを終了する。
-		// 同じ色の石が見つかったときのみ、異なる色の石を反転させる。
-        
-        //int s_row, s_col;
-        let mut s_row: i8;
-        let mut s_col: i8;
-		// ICellState oppositeColorDisk = disk.getReverseDisk();
-        let opposite_color_stone = stone.get_reverse_stone();
-        
-        // ArrayList<CellPos> flipCells = new ArrayList<CellPos>();
-        let mut flip_cells: Vec<CellPos> = Vec::new();
-
-
-        for dir in self.search_directions.iter() {
-            let mut opposite_color_cells: Vec<CellPos> = Vec::new();
-            s_row = row + dir.row;
-            s_col = col + dir.col;
-            while self.cells[s_row as usize][s_col as usize] == opposite_color_stone {
-                opposite_color_cells.push(CellPos{row: s_row, col: s_col});
-                s_row += dir.row;
-                s_col += dir.col;
-            }
-			// 最後に探索したセルが同色の石の場合、反転セルに追加する
-            if self.cells[s_row as usize][s_col as usize] == stone {
-                flip_cells.append(&mut opposite_color_cells);
-            }
+    }
+
+    fn bits_to_cells(&self, bits: Bits) -> Vec<CellPos> {
+        bits.iter().map(|index| self.index_to_pos(index)).collect()
+    }
+}
+
+impl Default for Board {
+    fn default() -> Board {
+        Board::new()
+    }
+}
+
+/// ビットボードの演算。盤面の大きさに応じてu64かBitsで計算する
+trait Lanes: Copy + BitAnd<Output = Self> + BitOr<Output = Self> + BitOrAssign {
+    const EMPTY: Self;
+    fn from_bits(bits: Bits) -> Self;
+    fn is_empty(self) -> bool;
+    fn shift(self, amount: i32) -> Self;
+}
+
+impl Lanes for u64 {
+    const EMPTY: u64 = 0;
+
+    fn from_bits(bits: Bits) -> u64 {
+        bits.low()
+    }
+
+    fn is_empty(self) -> bool {
+        self == 0
+    }
+
+    fn shift(self, amount: i32) -> u64 {
+        if amount > 0 {
+            self << amount
+        } else {
+            self >> -amount
         }
+    }
+}
+
+impl Lanes for Bits {
+    const EMPTY: Bits = Bits::EMPTY;
+
+    fn from_bits(bits: Bits) -> Bits {
+        bits
+    }
+
+    fn is_empty(self) -> bool {
+        Bits::is_empty(&self)
+    }
 
-        Ok(flip_cells)
-   }
-}
\ No newline at end of file
+    fn shift(self, amount: i32) -> Bits {
+        Bits::shift(self, amount)
+    }
+}
+
+/// amount方向にずらし、盤面の端を跨いだビットをmaskで落とす
+fn shift<T: Lanes>(bits: T, amount: i32, mask: Bits) -> T {
+    bits.shift(amount) & T::from_bits(mask)
+}
+
+/// 各方向について、自分の石から相手の石が続いた先の空きセルを集める
+#[inline(always)]
+fn legal_moves<T: Lanes>(own: T, opponent: T, blank: T, directions: &[(i32, Bits); 8], size: usize) -> T {
+    // 相手の石は1方向に最大size - 2個続く
+    let mut moves = T::EMPTY;
+    for &(amount, mask) in directions.iter() {
+        let mut candidates = shift(own, amount, mask) & opponent;
+        for _ in 0..size - 3 {
+            candidates |= shift(candidates, amount, mask) & opponent;
+        }
+        moves |= shift(candidates, amount, mask) & blank;
+    }
+    moves
+}
+
+/// 各方向について、putから相手の石が続いた先に自分の石があれば、その間の石を集める
+#[inline(always)]
+fn flips<T: Lanes>(put: T, own: T, opponent: T, directions: &[(i32, Bits); 8]) -> T {
+    let mut flips = T::EMPTY;
+    for &(amount, mask) in directions.iter() {
+        let mut line = T::EMPTY;
+        let mut cursor = shift(put, amount, mask);
+        while !(cursor & opponent).is_empty() {
+            line |= cursor;
+            cursor = shift(cursor, amount, mask);
+        }
+        if !(cursor & own).is_empty() {
+            flips |= line;
+        }
+    }
+    flips
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 石を置き、パスも1手と数えてdepth手先までの局面数を数える
+    fn perft(board: &Board, stone: CellState, depth: u32, passed: bool) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let moves = board.legal_moves_bits(stone);
+        if moves.is_empty() {
+            return if passed { 1 } else { perft(board, stone.get_reverse_stone(), depth - 1, true) };
+        }
+        moves.iter()
+            .map(|index| {
+                let mut next = *board;
+                next.put_bits(index, board.flip_bits(index, stone), stone);
+                perft(&next, stone.get_reverse_stone(), depth - 1, false)
+            })
+            .sum()
+    }
+
+    fn empty_board(size: usize) -> Board {
+        Board { size, black: Bits::EMPTY, white: Bits::EMPTY, blocked: Bits::EMPTY }
+    }
+
+    #[test]
+    fn initial_legal_moves() {
+        let board = Board::new();
+        let expected = board.pos_to_bit(3, 4) | board.pos_to_bit(4, 3) | board.pos_to_bit(5, 6) | board.pos_to_bit(6, 5);
+        assert_eq!(board.legal_moves_bits(CellState::BlackStone), expected);
+        assert_eq!(board.legal_moves(CellState::WhiteStone).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn initial_position_has_four_legal_moves_for_each_size() {
+        for size in (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).step_by(2) {
+            let board = Board::with_size(size).unwrap();
+            assert_eq!(board.legal_moves_bits(CellState::BlackStone).count_ones(), 4, "size {}", size);
+            assert_eq!(board.legal_moves(CellState::WhiteStone).unwrap().len(), 4, "size {}", size);
+        }
+    }
+
+    #[test]
+    fn invalid_sizes_are_rejected() {
+        for size in [0, 2, 5, 9, 18] {
+            assert!(Board::with_size(size).is_err(), "size {}", size);
+        }
+    }
+
+    #[test]
+    fn perft_matches_known_values() {
+        let board = Board::new();
+        let expected = [1, 4, 12, 56, 244, 1396, 8200];
+        for (depth, count) in expected.iter().enumerate() {
+            assert_eq!(perft(&board, CellState::BlackStone, depth as u32, false), *count, "depth {}", depth);
+        }
+    }
+
+    #[test]
+    fn shift_does_not_wrap_around_the_edge() {
+        // 右端のセルの次のビットは1つ下の行の左端だが、盤面上はつながっていない
+        for size in (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).step_by(2) {
+            let mut board = empty_board(size);
+            board.set_cell(2, size - 1, CellState::BlackStone).unwrap();
+            board.set_cell(2, size, CellState::WhiteStone).unwrap();
+            let index = board.pos_to_index(3, 1);
+            assert!(board.flip_bits(index, CellState::BlackStone).is_empty(), "size {}", size);
+            assert!(!board.legal_moves_bits(CellState::BlackStone).contains(index), "size {}", size);
+        }
+    }
+
+    #[test]
+    fn put_flips_lines_in_several_directions() {
+        let mut board = empty_board(8);
+        board.set_cell(4, 4, CellState::BlackStone).unwrap();
+        board.set_cell(4, 5, CellState::WhiteStone).unwrap();
+        board.set_cell(4, 6, CellState::WhiteStone).unwrap();
+        board.set_cell(5, 7, CellState::WhiteStone).unwrap();
+        board.set_cell(6, 7, CellState::BlackStone).unwrap();
+        let index = board.pos_to_index(4, 7);
+        let flips = board.flip_bits(index, CellState::BlackStone);
+        assert_eq!(flips, board.pos_to_bit(4, 5) | board.pos_to_bit(4, 6) | board.pos_to_bit(5, 7));
+
+        board.put_bits(index, flips, CellState::BlackStone);
+        assert_eq!(board.count_black_stones(), 6);
+        assert_eq!(board.count_white_stones(), 0);
+    }
+
+    #[test]
+    fn flip_crosses_word_boundary_on_10x10() {
+        let mut board = empty_board(10);
+        // 5列目の縦の並び。ビット番号は44, 54, 64, 74で、64から上位のワードに入る
+        board.set_cell(5, 5, CellState::BlackStone).unwrap();
+        board.set_cell(6, 5, CellState::WhiteStone).unwrap();
+        board.set_cell(7, 5, CellState::WhiteStone).unwrap();
+        let index = board.pos_to_index(8, 5);
+        assert_eq!(index, 74);
+
+        assert!(board.legal_moves_bits(CellState::BlackStone).contains(index));
+        let flips = board.flip_bits(index, CellState::BlackStone);
+        assert_eq!(flips, board.pos_to_bit(6, 5) | board.pos_to_bit(7, 5));
+
+        board.put_bits(index, flips, CellState::BlackStone);
+        assert_eq!(board.count_black_stones(), 4);
+        assert_eq!(board.count_white_stones(), 0);
+    }
+
+    #[test]
+    fn flip_reaches_the_last_word_on_16x16() {
+        let mut board = empty_board(16);
+        // 右下がりの斜め。ビット番号は153, 170, 187, 204で、192から最上位のワードに入る
+        board.set_cell(10, 10, CellState::WhiteStone).unwrap();
+        board.set_cell(11, 11, CellState::BlackStone).unwrap();
+        board.set_cell(12, 12, CellState::BlackStone).unwrap();
+        board.set_cell(13, 13, CellState::WhiteStone).unwrap();
+        let index = board.pos_to_index(10, 10);
+        assert_eq!(board.pos_to_index(13, 13), 204);
+
+        let mut moved = board;
+        moved.set_cell(10, 10, CellState::BlankCell).unwrap();
+        let flips = moved.flip_bits(index, CellState::WhiteStone);
+        assert_eq!(flips, board.pos_to_bit(11, 11) | board.pos_to_bit(12, 12));
+        assert!(moved.legal_moves_bits(CellState::WhiteStone).contains(index));
+    }
+
+    #[test]
+    fn blocked_cell_is_neither_legal_nor_flippable() {
+        let mut board = Board::new();
+        board.set_cell(4, 3, CellState::OuterCell).unwrap();
+        let index = board.pos_to_index(4, 3);
+        assert!(!board.legal_moves_bits(CellState::BlackStone).contains(index));
+        assert!(!board.blank_bits().contains(index));
+        assert_eq!(board.get_cell(4, 3), Some(CellState::OuterCell));
+        assert!(board.has_blocked_cell());
+    }
+
+    #[test]
+    fn blocked_cell_stops_a_flipping_line() {
+        let mut board = empty_board(8);
+        board.set_cell(4, 2, CellState::BlackStone).unwrap();
+        board.set_cell(4, 3, CellState::OuterCell).unwrap();
+        board.set_cell(4, 4, CellState::WhiteStone).unwrap();
+        let index = board.pos_to_index(4, 5);
+        assert!(board.flip_bits(index, CellState::BlackStone).is_empty());
+        assert!(!board.legal_moves_bits(CellState::BlackStone).contains(index));
+    }
+}
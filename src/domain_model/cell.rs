@@ -2,21 +2,20 @@ use std::fmt;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum CellState {
-    OuterCell, // 8x8の外側
+    OuterCell, // 盤面の外側か、盤面内の置けないセル
     BlankCell, // 石が置いてないセル
     BlackStone, // 黒
     WhiteStone, // 白
 }
 
+/// 局面の文字列と同じ1文字の表記。画面に表示する石はメッセージの側で作る
 impl fmt::Display for CellState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
-            CellState::OuterCell => "  ",
-            CellState::BlankCell => "  ",
-            CellState::BlackStone => "黒",
-            CellState::WhiteStone => "白",
-            // CellState::BlackStone => "●",
-            // CellState::WhiteStone => "○",
+            CellState::OuterCell => "#",
+            CellState::BlankCell => "-",
+            CellState::BlackStone => "X",
+            CellState::WhiteStone => "O",
         };
         write!(f, "{}", s)
     }    
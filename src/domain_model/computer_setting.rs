@@ -0,0 +1,42 @@
+use std::fmt;
+
+use crate::domain_model::turn::Turn;
+
+/// コンピュータの強さ
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AiLevel {
+    Random,       // ランダム。置ける場所から無作為に選ぶ
+    Greedy,       // 貪欲。最も多く反転できる場所を選ぶ
+    Beginner,     // 初級
+    Intermediate, // 中級
+    Strong,       // 上級
+    Maximum,      // 最強
+}
+
+impl AiLevel {
+    pub const ALL: [AiLevel; 6] = [
+        AiLevel::Random, AiLevel::Greedy, AiLevel::Beginner, AiLevel::Intermediate, AiLevel::Strong, AiLevel::Maximum,
+    ];
+}
+
+/// 識別用の名前。画面に表示する名前はメッセージの側で作る
+impl fmt::Display for AiLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            AiLevel::Random => "random",
+            AiLevel::Greedy => "greedy",
+            AiLevel::Beginner => "beginner",
+            AiLevel::Intermediate => "intermediate",
+            AiLevel::Strong => "strong",
+            AiLevel::Maximum => "maximum",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// コンピュータが打つターンと強さ
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComputerSetting {
+    pub turn: Turn,
+    pub level: AiLevel,
+}
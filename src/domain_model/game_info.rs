@@ -0,0 +1,8 @@
+/// 対局者名や開始日時など、盤面以外のゲームの情報
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameInfo {
+    pub black_player: String,
+    pub white_player: String,
+    pub started_at: String, // 開始日時(ISO 8601)。分からなければ空
+    pub slot: String,       // 保存先のスロット名。まだ保存していなければ空
+}
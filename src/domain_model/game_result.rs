@@ -0,0 +1,29 @@
+use crate::domain_model::turn::Turn;
+use crate::domain_model::variant::Variant;
+
+/// 勝敗
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Win(Turn), // 勝ったプレーヤー
+    Draw,      // 引き分け
+}
+
+/// ゲーム終了の理由
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EndReason {
+    BoardFull,         // 盤面が全て埋まった
+    NoMoves,           // 両者とも置ける場所がない
+    Resignation(Turn), // 投了したプレーヤー
+    Timeout(Turn),     // 時間切れになったプレーヤー
+}
+
+/// ゲームの結果
+#[derive(Debug, Clone)]
+pub struct GameResult {
+    pub outcome: Outcome,
+    pub black_count: i16,
+    pub white_count: i16,
+    pub empty_count: i16,
+    pub end_reason: EndReason,
+    pub variant: Variant, // 勝敗を決めたルール
+}
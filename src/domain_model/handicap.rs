@@ -0,0 +1,33 @@
+use crate::domain_model::board::Board;
+use crate::domain_model::cell::CellState;
+use crate::domain_model::turn::Turn;
+use crate::error::ReversiError;
+
+/// ハンデとして置ける隅の石の最大数
+pub const MAX_HANDICAP_CORNERS: usize = 4;
+
+/// ハンデ戦の設定。下手のturn側が、隅にcorners個の自分の石を置いた局面から始める
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Handicap {
+    pub turn: Turn,
+    pub corners: usize,
+}
+
+impl Handicap {
+    /// boardの隅に石を置く。左上、右下、右上、左下の順に使う
+    pub fn apply(&self, board: &mut Board) -> Result<(), ReversiError> {
+        if !(1..=MAX_HANDICAP_CORNERS).contains(&self.corners) {
+            return Err(ReversiError::InvalidHandicap { max: MAX_HANDICAP_CORNERS });
+        }
+
+        let size = board.size();
+        let stone = match self.turn {
+            Turn::Black => CellState::BlackStone,
+            Turn::White => CellState::WhiteStone,
+        };
+        for &(row, col) in [(1, 1), (size, size), (1, size), (size, 1)].iter().take(self.corners) {
+            board.set_cell(row, col, stone)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,8 @@
+use crate::domain_model::cell_pos::CellPos;
+
+/// 合法手。石を置けるセルと、置いたときに反転するセルの組
+#[derive(Debug, Clone)]
+pub struct LegalMove {
+    pub put_pos: CellPos,
+    pub flippable_cells: Vec<CellPos>,
+}
@@ -1,6 +1,13 @@
 pub mod board;
+pub mod bits;
 pub mod reversi_state;
 pub mod cell_pos;
 pub mod r#move;
 pub mod turn;
 pub mod cell;
+pub mod legal_move;
+pub mod game_result;
+pub mod computer_setting;
+pub mod game_info;
+pub mod variant;
+pub mod handicap;
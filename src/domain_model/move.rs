@@ -4,6 +4,14 @@ use crate::domain_model::cell_pos::CellPos;
 #[derive(Debug, Clone)]
 pub struct Move {
     pub turn: Turn,
-    pub put_pos: CellPos,
+    pub put_pos: Option<CellPos>, // パスの場合はNone
     pub flipped_pos_list: Vec<CellPos>,
-}
\ No newline at end of file
+    pub eval: Option<f64>,      // 評価値。GGFなどから読み込んだ手だけにある
+    pub time: Option<String>,   // 消費時間。GGFなどから読み込んだ手だけにある
+}
+
+impl Move {
+    pub fn is_pass(&self) -> bool {
+        self.put_pos.is_none()
+    }
+}
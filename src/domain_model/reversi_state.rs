@@ -4,31 +4,72 @@ use crate::domain_model::cell::CellState;
 use super::board::Board;
 use super::r#move::Move;
 use super::turn::Turn;
+use super::game_result::EndReason;
+use super::computer_setting::ComputerSetting;
+use super::game_info::GameInfo;
+use super::variant::Variant;
 
 pub struct ReversiState {
     pub board: Board,
     pub turn: Turn,
     pub gameover: bool,
+    pub end_reason: Option<EndReason>,
     pub undo_buffer: Vec<Move>,
-    pub redo_buffer: Vec<Move>
+    pub redo_buffer: Vec<Move>,
+    pub computer: Option<ComputerSetting>, // コンピュータと対戦しない場合はNone
+    pub initial_board: Board, // 開始局面
+    pub initial_turn: Turn,   // 開始局面の手番
+    pub variant: Variant,     // 勝敗の決め方
+    pub info: GameInfo,
 }
 
 impl ReversiState {
     pub fn new() -> ReversiState {
+        ReversiState::with_position(Board::new(), Turn::Black)
+    }
+
+    /// boardの局面、turnの手番から始めるゲーム状態
+    pub fn with_position(board: Board, turn: Turn) -> ReversiState {
+        ReversiState::with_variant(board, turn, Variant::Standard)
+    }
+
+    /// boardの局面、turnの手番から、variantのルールで始めるゲーム状態
+    pub fn with_variant(board: Board, turn: Turn, variant: Variant) -> ReversiState {
         ReversiState {
-            board: Board::new(),
-            turn: Turn::Black,
+            board,
+            turn,
             gameover: false,
+            end_reason: None,
             undo_buffer: Vec::new(),
             redo_buffer: Vec::new(),
+            computer: None,
+            initial_board: board,
+            initial_turn: turn,
+            variant,
+            info: GameInfo::default(),
         }
     }
 
     pub fn register_move(&mut self, row: usize, col: usize, flipped_cells: Vec<CellPos>) {
         let r#move = Move { 
             turn: self.turn,
-            put_pos: CellPos { row: row as i8, col: col as i8},
+            put_pos: Some(CellPos { row: row as i8, col: col as i8}),
             flipped_pos_list: flipped_cells,
+            eval: None,
+            time: None,
+        };
+
+        self.undo_buffer.push(r#move);
+    }
+
+    /// パスを記録する
+    pub fn register_pass(&mut self) {
+        let r#move = Move {
+            turn: self.turn,
+            put_pos: None,
+            flipped_pos_list: Vec::new(),
+            eval: None,
+            time: None,
         };
 
         self.undo_buffer.push(r#move);
@@ -56,14 +97,18 @@ impl ReversiState {
     pub fn undo(&mut self) -> Result<(), ReversiError> {
         let last_move = match self.undo_buffer.pop() {
             Some(mv) => mv,
-            None => return Err(ReversiError::new("履歴がありません"))
+            None => return Err(ReversiError::NothingToUndo)
         };
  
-        self.board.flip(&last_move.flipped_pos_list);
-        self.board.set_cell(
-            last_move.put_pos.row as usize, last_move.put_pos.col as usize, 
-            CellState::BlankCell)?;
+        if let Some(put_pos) = &last_move.put_pos {
+            self.board.flip(&last_move.flipped_pos_list);
+            self.board.set_cell(
+                put_pos.row as usize, put_pos.col as usize, 
+                CellState::BlankCell)?;
+        }
         self.turn = last_move.turn;
+        self.gameover = false;
+        self.end_reason = None;
 
         self.redo_buffer.push(last_move);
 
@@ -73,18 +118,26 @@ impl ReversiState {
     pub fn redo(&mut self) -> Result<(), ReversiError> {
         let redo_move = match self.redo_buffer.pop() {
             Some(mv) => mv,
-            None => return Err(ReversiError::new("redoバッファが空です"))
+            None => return Err(ReversiError::NothingToRedo)
         };
 
-        self.board.flip(&redo_move.flipped_pos_list);
-        self.board.set_cell(
-            redo_move.put_pos.row as usize,
-            redo_move.put_pos.col as usize,
-            if redo_move.turn == Turn::Black { CellState::BlackStone } else { CellState::WhiteStone })?;
+        if let Some(put_pos) = &redo_move.put_pos {
+            self.board.flip(&redo_move.flipped_pos_list);
+            self.board.set_cell(
+                put_pos.row as usize,
+                put_pos.col as usize,
+                if redo_move.turn == Turn::Black { CellState::BlackStone } else { CellState::WhiteStone })?;
+        }
         self.turn = if redo_move.turn == Turn::Black { Turn::White } else { Turn::Black };
 
         self.undo_buffer.push(redo_move);
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+impl Default for ReversiState {
+    fn default() -> ReversiState {
+        ReversiState::new()
+    }
+}
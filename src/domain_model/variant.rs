@@ -0,0 +1,19 @@
+/// ルールの種類。着手のルールはどれも同じで、勝敗の決め方が違う
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Variant {
+    #[default]
+    Standard, // 石の多い方が勝ち
+    Anti,     // 石の少ない方が勝ち(アンチリバーシ)
+}
+
+impl Variant {
+    pub const ALL: [Variant; 2] = [Variant::Standard, Variant::Anti];
+
+    /// 自分の石がown個、相手の石がopponent個で終局したときの石差。勝っていれば正になる
+    pub fn disc_margin(self, own: i32, opponent: i32) -> i32 {
+        match self {
+            Variant::Standard => own - opponent,
+            Variant::Anti => opponent - own,
+        }
+    }
+}
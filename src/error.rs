@@ -1,21 +1,244 @@
 use std::error;
 use std::fmt;
+use std::io;
 
-#[derive(Debug, Clone)]
-pub struct ReversiError {
-    pub message: String
+use crate::domain_model::cell::CellState;
+use crate::domain_model::turn::Turn;
+
+/// ルール違反や、ファイルの読み書きの失敗。
+/// 利用者向けのメッセージは画面側で作る。Displayは開発者向けの簡単な説明
+#[derive(Debug)]
+pub enum ReversiError {
+    // ルール違反
+    /// 盤面の範囲外
+    OutOfRange { row: usize, col: usize },
+    /// 既に石が置かれている
+    Occupied { row: usize, col: usize },
+    /// 置けないセル
+    Blocked { row: usize, col: usize },
+    /// 反転できる石がない
+    NoFlips { row: usize, col: usize },
+    /// 石ではないセルの状態を指定した
+    NotAStone(CellState),
+    /// ゲームは終了している
+    GameOver,
+    /// expected側の手番である
+    WrongTurn { expected: Turn },
+    /// 置ける場所があるのにパスした
+    IllegalPass { turn: Turn },
+    /// 置ける場所がないのでパスのはずが、パスしていない
+    MissingPass { turn: Turn },
+    /// 戻せる手がない
+    NothingToUndo,
+    /// 進められる手がない
+    NothingToRedo,
+    /// 手数が0～maxの範囲外
+    MoveNumberOutOfRange { max: usize },
+    /// 空きマスが多すぎて完全読みできない
+    TooManyEmpties { empties: u32, max: u32 },
+    /// 使えない盤面の大きさ
+    InvalidBoardSize(usize),
+    /// ハンデの隅の石の数が1～maxの範囲外
+    InvalidHandicap { max: usize },
+    /// どちらも置ける場所がなく、対局できない局面
+    NoLegalMoves,
+    /// 棋譜の形式では表せない、8x8の初期配置以外から始めたゲーム
+    NonStandardStart,
+    /// GGFでは表せない、置けないセルのある盤面のゲーム
+    BlockedCellsInGgf,
+
+    // 保存スロット
+    /// スロット名が空
+    EmptySlotName,
+    /// スロット名がmax文字より長い
+    SlotNameTooLong { max: usize },
+    /// スロット名に使えない文字がある
+    InvalidSlotName(String),
+    /// スロットがない
+    SlotNotFound(String),
+    /// スロットが既にある
+    SlotExists(String),
+
+    // 入出力とファイル形式
+    /// ファイルの読み書きの失敗
+    Io(io::Error),
+    /// JSONの読み書きの失敗
+    Json(serde_json::Error),
+    /// ファイルや文字列の形式が正しくない
+    Parse(ParseErrorKind),
+    /// ファイル内のlocationの位置でのエラー
+    At { location: Location, source: Box<ReversiError> },
+}
+
+/// 形式が正しくない理由
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    /// 手の表記が正しくない
+    InvalidMove(String),
+    /// 盤面の文字が正しくない
+    InvalidCell(char),
+    /// 手番の表記が正しくない
+    InvalidTurn(String),
+    /// 数値が正しくない
+    InvalidNumber(String),
+    /// fieldの値が正しくない
+    InvalidValue { field: &'static str, value: String },
+    /// 文字数がexpectedではない
+    InvalidLength { expected: usize, actual: usize },
+    /// ファイルが途中で切れている
+    Truncated,
+    /// レコードがexpected件あるはずがactual件しかない
+    MissingRecords { expected: usize, actual: usize },
+    /// undoの手数が記録された手の数より多い
+    UndoCountTooLarge { count: usize, moves: usize },
+    /// ゲームが閉じられていない
+    UnclosedGame,
+    /// ゲームが見つからない
+    NoGame,
+    /// プロパティの形式が正しくない
+    MalformedProperty(String),
+    /// オセロ以外のゲーム
+    NotOthello(String),
+    /// 対応していない盤面の大きさ
+    UnsupportedBoardSize(String),
+    /// 対応していない保存ファイルのバージョン
+    UnsupportedVersion(u32),
+    /// 対応していないルール
+    UnsupportedVariant(String),
+}
+
+/// ファイル内の位置
+#[derive(Debug, Clone, PartialEq)]
+pub enum Location {
+    /// 行番号(1から)
+    Line(usize),
+    /// 何手目か(1から)
+    Move(usize),
+    /// 何番目のゲームか(1から)
+    Game(usize),
+    /// 保存ファイルのlistの何番目か(1から)
+    Entry { list: &'static str, index: usize },
+    /// 盤面のセル
+    Cell { row: usize, col: usize },
+    /// ファイルの最後
+    EndOfFile,
 }
 
 impl ReversiError {
-    pub fn new(message: impl Into<String>) -> ReversiError {
-        ReversiError { message: message.into() }
+    /// locationの位置でのエラーにする
+    pub fn at(self, location: Location) -> ReversiError {
+        ReversiError::At { location, source: Box::new(self) }
+    }
+
+    /// 位置の情報を除いた、元のエラー
+    pub fn root(&self) -> &ReversiError {
+        match self {
+            ReversiError::At { source, .. } => source.root(),
+            _ => self,
+        }
+    }
+}
+
+impl From<io::Error> for ReversiError {
+    fn from(e: io::Error) -> ReversiError {
+        ReversiError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ReversiError {
+    fn from(e: serde_json::Error) -> ReversiError {
+        ReversiError::Json(e)
+    }
+}
+
+impl From<ParseErrorKind> for ReversiError {
+    fn from(kind: ParseErrorKind) -> ReversiError {
+        ReversiError::Parse(kind)
     }
 }
 
 impl fmt::Display for ReversiError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.message)
+        match self {
+            ReversiError::OutOfRange { row, col } => write!(f, "cell ({}, {}) is out of range", row, col),
+            ReversiError::Occupied { row, col } => write!(f, "cell ({}, {}) is already occupied", row, col),
+            ReversiError::Blocked { row, col } => write!(f, "cell ({}, {}) is blocked", row, col),
+            ReversiError::NoFlips { row, col } => write!(f, "no stones to flip at ({}, {})", row, col),
+            ReversiError::NotAStone(cell) => write!(f, "{:?} is not a stone", cell),
+            ReversiError::GameOver => write!(f, "the game is over"),
+            ReversiError::WrongTurn { expected } => write!(f, "it is {:?}'s turn", expected),
+            ReversiError::IllegalPass { turn } => write!(f, "{:?} passed although a move is available", turn),
+            ReversiError::MissingPass { turn } => write!(f, "{:?} has no legal move and must pass", turn),
+            ReversiError::NothingToUndo => write!(f, "no move to undo"),
+            ReversiError::NothingToRedo => write!(f, "no move to redo"),
+            ReversiError::MoveNumberOutOfRange { max } => write!(f, "move number must be 0 to {}", max),
+            ReversiError::TooManyEmpties { empties, max } =>
+                write!(f, "{} empty cells is too many to solve (max {})", empties, max),
+            ReversiError::InvalidBoardSize(size) => write!(f, "invalid board size: {}", size),
+            ReversiError::InvalidHandicap { max } => write!(f, "handicap must be 1 to {} corners", max),
+            ReversiError::NoLegalMoves => write!(f, "neither side has a legal move"),
+            ReversiError::NonStandardStart => write!(f, "the move list format cannot represent this game"),
+            ReversiError::BlockedCellsInGgf => write!(f, "GGF cannot represent blocked cells"),
+            ReversiError::EmptySlotName => write!(f, "slot name is empty"),
+            ReversiError::SlotNameTooLong { max } => write!(f, "slot name is longer than {} characters", max),
+            ReversiError::InvalidSlotName(name) => write!(f, "invalid slot name: {}", name),
+            ReversiError::SlotNotFound(name) => write!(f, "slot not found: {}", name),
+            ReversiError::SlotExists(name) => write!(f, "slot already exists: {}", name),
+            ReversiError::Io(e) => write!(f, "I/O error: {}", e),
+            ReversiError::Json(e) => write!(f, "JSON error: {}", e),
+            ReversiError::Parse(kind) => write!(f, "parse error: {}", kind),
+            // 元のエラーはsource()で辿れるので、位置だけを示す
+            ReversiError::At { location, .. } => write!(f, "error at {}", location),
+        }
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::InvalidMove(s) => write!(f, "invalid move: {}", s),
+            ParseErrorKind::InvalidCell(c) => write!(f, "invalid cell character: {}", c),
+            ParseErrorKind::InvalidTurn(s) => write!(f, "invalid side to move: {}", s),
+            ParseErrorKind::InvalidNumber(s) => write!(f, "invalid number: {}", s),
+            ParseErrorKind::InvalidValue { field, value } => write!(f, "invalid value for {}: {}", field, value),
+            ParseErrorKind::InvalidLength { expected, actual } =>
+                write!(f, "expected {} characters but found {}", expected, actual),
+            ParseErrorKind::Truncated => write!(f, "file is truncated"),
+            ParseErrorKind::MissingRecords { expected, actual } =>
+                write!(f, "expected {} records but found {}", expected, actual),
+            ParseErrorKind::UndoCountTooLarge { count, moves } =>
+                write!(f, "undo count {} exceeds the {} recorded moves", count, moves),
+            ParseErrorKind::UnclosedGame => write!(f, "game is not closed"),
+            ParseErrorKind::NoGame => write!(f, "no game found"),
+            ParseErrorKind::MalformedProperty(s) => write!(f, "malformed property: {}", s),
+            ParseErrorKind::NotOthello(s) => write!(f, "not an Othello game: {}", s),
+            ParseErrorKind::UnsupportedBoardSize(s) => write!(f, "unsupported board size: {}", s),
+            ParseErrorKind::UnsupportedVersion(v) => write!(f, "unsupported save format version: {}", v),
+            ParseErrorKind::UnsupportedVariant(s) => write!(f, "unsupported variant: {}", s),
+        }
     }
 }
 
-impl error::Error for ReversiError {}
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Location::Line(line) => write!(f, "line {}", line),
+            Location::Move(n) => write!(f, "move {}", n),
+            Location::Game(n) => write!(f, "game {}", n),
+            Location::Entry { list, index } => write!(f, "{} entry {}", list, index),
+            Location::Cell { row, col } => write!(f, "cell ({}, {})", row, col),
+            Location::EndOfFile => write!(f, "end of file"),
+        }
+    }
+}
+
+impl error::Error for ReversiError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ReversiError::Io(e) => Some(e),
+            ReversiError::Json(e) => Some(e),
+            ReversiError::At { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! リバーシ(オセロ)のライブラリ。盤面とルール、ゲームの進行、棋譜や保存ファイルの形式、コンピュータの思考を提供する
+//!
+//! - `domain_model`: 盤面、手、ゲーム状態などのモデル
+//! - `service`: 石を置く、戻す、保存するなどのゲームの操作
+//! - `data`: 保存ファイル、棋譜(GGF、WTHOR、局面の文字列など)の読み書き
+//! - `ai`: 評価関数、探索、終盤の完全読み
+//! - `player`: ゲームを進めるプレーヤー
+
+pub mod error;
+pub mod domain_model;
+pub mod service;
+pub mod data;
+pub mod ai;
+pub mod player;
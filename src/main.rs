@@ -1,10 +1,36 @@
-mod error;
 mod view;
-mod domain_model;
-mod service;
-mod data;
+
+use rust_reversi::service::reversi_service;
+use view::messages::{self, messages};
 
 fn main() {
-    view::title_view::show();
-}
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
 
+    // 表示する言語。--langはほかの指定と一緒に使える
+    let lang = match args.iter().position(|arg| arg == "--lang") {
+        Some(i) if i + 1 < args.len() => {
+            let code = args.remove(i + 1);
+            args.remove(i);
+            Some(code)
+        },
+        _ => None,
+    };
+    messages::init(lang.as_deref());
+
+    match args.as_slice() {
+        [] => view::title_view::show(),
+        [option, moves] if option == "--moves" => {
+            view::title_view::show_imported_game(reversi_service::import_move_string(moves));
+        },
+        [option, path] if option == "--moves-file" => {
+            view::title_view::show_imported_game(reversi_service::import_move_file(path));
+        },
+        [option, position] if option == "--position" => {
+            view::title_view::show_imported_game(reversi_service::import_position(position));
+        },
+        _ => {
+            println!("{}", messages().usage());
+            std::process::exit(1);
+        },
+    }
+}
@@ -0,0 +1,32 @@
+use crate::domain_model::reversi_state::ReversiState;
+use crate::player::{Player, PlayerAction};
+use crate::service::reversi_service;
+
+/// 最も多く反転できる場所を選ぶコンピュータ
+pub struct GreedyPlayer;
+
+impl GreedyPlayer {
+    pub fn new() -> GreedyPlayer {
+        GreedyPlayer
+    }
+}
+
+impl Default for GreedyPlayer {
+    fn default() -> GreedyPlayer {
+        GreedyPlayer::new()
+    }
+}
+
+impl Player for GreedyPlayer {
+    fn name(&self) -> String {
+        "greedy".to_string()
+    }
+
+    fn ask_move(&mut self, state: &ReversiState) -> PlayerAction {
+        let legal_moves = reversi_service::get_legal_moves(state).unwrap_or_default();
+        match legal_moves.iter().max_by_key(|m| m.flippable_cells.len()) {
+            Some(legal_move) => PlayerAction::Put(legal_move.put_pos.clone()),
+            None => PlayerAction::Resign,
+        }
+    }
+}
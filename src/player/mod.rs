@@ -0,0 +1,40 @@
+use crate::domain_model::cell_pos::CellPos;
+use crate::domain_model::game_result::GameResult;
+use crate::domain_model::r#move::Move;
+use crate::domain_model::reversi_state::ReversiState;
+
+pub mod random_player;
+pub mod greedy_player;
+pub mod search_player;
+
+/// プレーヤーが選んだ行動
+#[derive(Debug, Clone)]
+pub enum PlayerAction {
+    Put(CellPos),       // 石を置く
+    Resign,             // 投了する
+    Quit { save: bool }, // ゲームを中断する。saveがtrueなら保存する
+    Undo,               // 1手戻す
+    Redo,               // 1手進める
+    Jump(usize),        // 指定した手数の局面に移動する
+}
+
+/// ゲームの参加者。人、コンピュータ、通信相手などを同じように扱う
+pub trait Player {
+    /// 識別用の名前。"random"のようなASCIIの名前にし、画面に表示する名前はメッセージの側で作る
+    fn name(&self) -> String;
+
+    /// 画面から操作する人か
+    fn is_human(&self) -> bool {
+        false
+    }
+
+    /// 現在の局面で、現在のターンの行動を決める。
+    /// 置ける場所がないときは呼ばれない。ゲーム終了後は、人のプレーヤーにだけ振り返りのために呼ばれる
+    fn ask_move(&mut self, state: &ReversiState) -> PlayerAction;
+
+    /// 相手が打った手(パスを含む)の通知
+    fn notify_opponent_move(&mut self, _state: &ReversiState, _mv: &Move) {}
+
+    /// ゲーム終了の通知
+    fn notify_game_end(&mut self, _state: &ReversiState, _result: &GameResult) {}
+}
@@ -0,0 +1,34 @@
+use rand::seq::SliceRandom;
+
+use crate::domain_model::reversi_state::ReversiState;
+use crate::player::{Player, PlayerAction};
+use crate::service::reversi_service;
+
+/// 置ける場所からランダムに選ぶコンピュータ
+pub struct RandomPlayer;
+
+impl RandomPlayer {
+    pub fn new() -> RandomPlayer {
+        RandomPlayer
+    }
+}
+
+impl Default for RandomPlayer {
+    fn default() -> RandomPlayer {
+        RandomPlayer::new()
+    }
+}
+
+impl Player for RandomPlayer {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn ask_move(&mut self, state: &ReversiState) -> PlayerAction {
+        let legal_moves = reversi_service::get_legal_moves(state).unwrap_or_default();
+        match legal_moves.choose(&mut rand::thread_rng()) {
+            Some(legal_move) => PlayerAction::Put(legal_move.put_pos.clone()),
+            None => PlayerAction::Resign,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::ai::search;
+use crate::domain_model::computer_setting::AiLevel;
+use crate::domain_model::reversi_state::ReversiState;
+use crate::player::{Player, PlayerAction};
+
+/// 探索で手を選ぶコンピュータ
+pub struct SearchPlayer {
+    pub level: AiLevel,
+}
+
+impl SearchPlayer {
+    pub fn new(level: AiLevel) -> SearchPlayer {
+        SearchPlayer { level }
+    }
+}
+
+impl Player for SearchPlayer {
+    fn name(&self) -> String {
+        format!("search-{}", self.level)
+    }
+
+    fn ask_move(&mut self, state: &ReversiState) -> PlayerAction {
+        match search::choose_move(state, self.level) {
+            Some(pos) => PlayerAction::Put(pos),
+            None => PlayerAction::Resign,
+        }
+    }
+}
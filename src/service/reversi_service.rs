@@ -1,34 +1,46 @@
-use std::fs::File;
-use std::io::{self, Read, Write, BufRead, BufReader, BufWriter};
-use regex::Regex;
-
-use crate::error::ReversiError;
+use crate::error::{Location, ReversiError};
 use crate::domain_model::reversi_state::ReversiState;
 use crate::domain_model::turn::Turn;
-use crate::domain_model::cell::CellState;
+use crate::domain_model::cell::{CellState, ICellState};
+use crate::domain_model::r#move::Move;
+use crate::domain_model::legal_move::LegalMove;
 use crate::domain_model::cell_pos::CellPos;
+use crate::domain_model::game_result::{GameResult, Outcome, EndReason};
+use crate::data::fileio::{self, SaveData, SaveSlot};
+use crate::data::timestamp::Timestamp;
+use crate::data::move_list;
+use crate::data::ggf::{self, GgfGame, GgfMove};
+use crate::data::wthor::{self, WthorDatabase, WthorGame};
+use crate::data::position;
+use crate::data::layout;
+use crate::data::config::{self, Config};
 use crate::domain_model::board::Board;
-use crate::domain_model::r#move::Move;
-use crate::data::fileio;
+use crate::domain_model::variant::Variant;
+use crate::domain_model::handicap::Handicap;
+use crate::ai::endgame::{self, EndgameResult};
+use crate::ai::search;
+use crate::domain_model::computer_setting::AiLevel;
 
 pub fn put_stone(state: &mut ReversiState, row: usize, col: usize) -> Result<(), ReversiError> {
-    let stone = match state.turn {
-        Turn::Black => CellState::BlackStone,
-        Turn::White => CellState::WhiteStone,
-    };
+    if state.gameover {
+        return Err(ReversiError::GameOver);
+    }
+
+    let stone = turn_to_stone(state.turn);
 
     // 盤面の範囲内か確認
     if !state.board.is_in_range(row, col) {
-        return Err(ReversiError::new("盤面の範囲外です"));
+        return Err(ReversiError::OutOfRange { row, col });
     }
 
     // 既に置いてあるか確認
     match state.board.get_cell(row, col) {
         Some(cell) => match cell {
             CellState::BlankCell => {},
-            _ => { return Err(ReversiError::new("既に石が置かれています")) },
+            CellState::OuterCell => return Err(ReversiError::Blocked { row, col }),
+            _ => { return Err(ReversiError::Occupied { row, col }) },
         },
-        None => return Err(ReversiError::new(format!("石の位置が範囲外です: row={}, col={}", row, col))),
+        None => return Err(ReversiError::OutOfRange { row, col }),
     }
 
     // 反転できるか探索する
@@ -36,11 +48,11 @@ pub fn put_stone(state: &mut ReversiState, row: usize, col: usize) -> Result<(),
 
     // 反転する石があるか？
     if flip_cells.is_empty() { 
-        return Err(ReversiError::new("反転できる石がありません"))
+        return Err(ReversiError::NoFlips { row, col })
     }
 
     // 石を置いて
-    state.board.set_cell(row, col, stone);
+    state.board.set_cell(row, col, stone)?;
 
     // ひっくり返す
     state.board.flip(&flip_cells);
@@ -52,28 +64,111 @@ pub fn put_stone(state: &mut ReversiState, row: usize, col: usize) -> Result<(),
     // redoバッファはクリア
     state.redo_buffer.clear();
 
-    // ゲーム終了か調べる
-    if state.board.has_blank_cell() {
-        // BlankCellがあればゲーム継続
-        // ターンを切り替える
+    // 次の手番に進める
+    next_turn(state)?;
+
+    Ok(())
+}
+
+/// 現在の局面を最後まで読み切り、最終的な石差と最善進行を求める
+pub fn analyze_endgame(state: &ReversiState) -> Result<EndgameResult, ReversiError> {
+    endgame::solve(state)
+}
+
+/// levelの強さで探索した、現在のターンのプレーヤーの最善手。置ける場所がなければNone
+pub fn suggest_move(state: &ReversiState, level: AiLevel) -> Option<CellPos> {
+    search::choose_move(state, level)
+}
+
+/// ターンを切り替える。
+/// 次のプレーヤーが置けなければパスを記録して手番を戻し、
+/// どちらのプレーヤーも置けなければゲーム終了とする。
+fn next_turn(state: &mut ReversiState) -> Result<(), ReversiError> {
+    switch_turn(state);
+    if can_put_stone(state)? {
+        return Ok(());
+    }
+
+    switch_turn(state);
+    if can_put_stone(state)? {
+        // 相手だけ置けないのでパス
         switch_turn(state);
+        pass(state);
     } else {
-        // BlankCellがなければゲーム終了
-        state.gameover = true;
+        // 両者とも置けないのでゲーム終了
+        end_game(state, no_moves_reason(state));
     }
 
     Ok(())
 }
 
-pub fn get_result_string(state: &ReversiState) -> String {
-    format!("{}の勝ち", if state.board.count_black_stones() > 32 {"黒"} else {"白"})
+/// 現在のターンのプレーヤーが置けるセルがあるか
+pub fn can_put_stone(state: &ReversiState) -> Result<bool, ReversiError> {
+    state.board.has_legal_move(turn_to_stone(state.turn))
+}
+
+fn turn_to_stone(turn: Turn) -> CellState {
+    match turn {
+        Turn::Black => CellState::BlackStone,
+        Turn::White => CellState::WhiteStone,
+    }
+}
+
+/// 直前の手がパスだったか
+pub fn last_move_is_pass(state: &ReversiState) -> bool {
+    match state.undo_buffer.last() {
+        Some(mv) => mv.is_pass(),
+        None => false,
+    }
+}
+
+/// 現在のターンのプレーヤーが置けるセルの一覧
+pub fn get_legal_moves(state: &ReversiState) -> Result<Vec<LegalMove>, ReversiError> {
+    state.board.legal_moves(turn_to_stone(state.turn))
+}
+
+/// ゲームの結果を返す。ゲームが終わっていなければNone
+pub fn get_result(state: &ReversiState) -> Option<GameResult> {
+    let end_reason = state.end_reason?;
+    let black_count = state.board.count_black_stones();
+    let white_count = state.board.count_white_stones();
+
+    let outcome = match end_reason {
+        // 投了・時間切れは相手の勝ち
+        EndReason::Resignation(loser) | EndReason::Timeout(loser) => Outcome::Win(opposite_turn(loser)),
+        EndReason::BoardFull | EndReason::NoMoves => {
+            let black_margin = state.variant.disc_margin(black_count as i32, white_count as i32);
+            if black_margin > 0 {
+                Outcome::Win(Turn::Black)
+            } else if black_margin < 0 {
+                Outcome::Win(Turn::White)
+            } else {
+                Outcome::Draw
+            }
+        },
+    };
+
+    Some(GameResult {
+        outcome,
+        black_count,
+        white_count,
+        empty_count: state.board.count_blank_cells(),
+        end_reason,
+        variant: state.variant,
+    })
+}
+
+fn opposite_turn(turn: Turn) -> Turn {
+    if turn == Turn::Black { Turn::White } else { Turn::Black }
 }
 
 pub fn switch_turn(state: &mut ReversiState) {
-    state.turn = if state.turn == Turn::Black { Turn::White } else { Turn::Black }
+    state.turn = opposite_turn(state.turn);
 }
 
+/// 現在のターンのプレーヤーがパスする
 pub fn pass(state: &mut ReversiState) {
+    state.register_pass();
     switch_turn(state);
 }
 
@@ -84,68 +179,841 @@ pub fn get_history_size(state: &ReversiState) -> usize {
 // init_state() は不要な気がする
 
 pub fn undo(state: &mut ReversiState) -> Result<(), ReversiError> {
-    state.undo()
+    // 開始局面で置けずにパスした場合、そのパスは取り消さない
+    if state.undo_buffer.iter().all(|mv| mv.is_pass()) {
+        return Err(ReversiError::NothingToUndo);
+    }
+    state.undo()?;
+
+    // パスを取り消したときは、パスの原因となった手も取り消す
+    if let Some(mv) = state.redo_buffer.last() {
+        if mv.is_pass() && !state.undo_buffer.is_empty() {
+            state.undo()?;
+        }
+    }
+
+    Ok(())
 }
 
 pub fn redo(state: &mut ReversiState) -> Result<(), ReversiError> {
-    state.redo()
+    state.redo()?;
+
+    // 続くパスもやり直す
+    if let Some(mv) = state.redo_buffer.last() {
+        if mv.is_pass() {
+            state.redo()?;
+        }
+    }
+    update_gameover(state)
+}
+
+/// move_number手目を打った直後の局面に移動する。0なら初期局面
+pub fn jump_to(state: &mut ReversiState, move_number: usize) -> Result<(), ReversiError> {
+    let total = state.undo_buffer.len() + state.redo_buffer.len();
+    if move_number > total {
+        return Err(ReversiError::MoveNumberOutOfRange { max: total });
+    }
+
+    while state.undo_buffer.len() > move_number {
+        state.undo()?;
+    }
+    while state.undo_buffer.len() < move_number {
+        state.redo()?;
+    }
+
+    // 次がパスなら、パスした後の局面にする
+    if let Some(mv) = state.redo_buffer.last() {
+        if mv.is_pass() {
+            state.redo()?;
+        }
+    }
+    update_gameover(state)
+}
+
+/// 両者とも置けなければゲーム終了とする
+fn update_gameover(state: &mut ReversiState) -> Result<(), ReversiError> {
+    let stone = turn_to_stone(state.turn);
+    if !state.board.has_legal_move(stone)? && !state.board.has_legal_move(stone.get_reverse_stone())? {
+        end_game(state, no_moves_reason(state));
+    }
+    Ok(())
+}
+
+/// 両者とも置けなくなったときの終了理由
+fn no_moves_reason(state: &ReversiState) -> EndReason {
+    if state.board.has_blank_cell() { EndReason::NoMoves } else { EndReason::BoardFull }
+}
+
+fn end_game(state: &mut ReversiState, reason: EndReason) {
+    state.gameover = true;
+    state.end_reason = Some(reason);
+}
+
+/// 現在のターンのプレーヤーが投了する
+pub fn resign(state: &mut ReversiState) -> Result<(), ReversiError> {
+    if state.gameover {
+        return Err(ReversiError::GameOver);
+    }
+    end_game(state, EndReason::Resignation(state.turn));
+    Ok(())
+}
+
+/// 現在のターンのプレーヤーが時間切れで負けとなる
+pub fn timeout(state: &mut ReversiState) -> Result<(), ReversiError> {
+    if state.gameover {
+        return Err(ReversiError::GameOver);
+    }
+    end_game(state, EndReason::Timeout(state.turn));
+    Ok(())
+}
+
+
+/// ゲーム状態をslotに保存する。以降はそのスロットのゲームとして扱う
+pub fn save(state: &mut ReversiState, slot: &str) -> Result<(), ReversiError> {
+    fileio::write_slot(slot, state, get_result(state).as_ref())?;
+    state.info.slot = slot.to_string();
+    Ok(())
+}
+
+/// 新しく保存するときの既定のスロット名。保存済みならそのスロット名
+pub fn default_save_name(state: &ReversiState) -> String {
+    if !state.info.slot.is_empty() {
+        return state.info.slot.clone();
+    }
+    let now = Timestamp::now();
+    format!("game-{:04}{:02}{:02}-{:02}{:02}{:02}", now.year, now.month, now.day, now.hour, now.minute, now.second)
+}
+
+/// スロット名として使えるか確かめる
+pub fn validate_save_name(slot: &str) -> Result<(), ReversiError> {
+    fileio::validate_slot_name(slot)
+}
+
+/// 保存スロットの一覧
+pub fn list_saves() -> Result<Vec<SaveSlot>, ReversiError> {
+    fileio::list_slots()
+}
+
+/// 保存スロットがあるか
+pub fn save_exists(slot: &str) -> bool {
+    fileio::slot_exists(slot)
+}
+
+/// 保存スロットを削除する
+pub fn delete_save(slot: &str) -> Result<(), ReversiError> {
+    fileio::delete_slot(slot)
+}
+
+/// 保存スロットの名前を変える
+pub fn rename_save(slot: &str, new_slot: &str) -> Result<(), ReversiError> {
+    fileio::rename_slot(slot, new_slot)
+}
+
+/// slotに保存したゲーム状態を読み込む
+pub fn load(slot: &str) -> Result<ReversiState, ReversiError> {
+    let mut state = fileio::read_slot(slot).and_then(restore)?;
+    state.info.slot = slot.to_string();
+    Ok(state)
+}
+
+/// 中断に備えて、ゲーム状態を自動保存する
+pub fn autosave(state: &ReversiState) -> Result<(), ReversiError> {
+    fileio::write_autosave(state, get_result(state).as_ref())
+}
+
+/// 中断したゲームの自動保存があるか
+pub fn has_autosave() -> bool {
+    fileio::autosave_exists()
+}
+
+/// 中断したゲームを自動保存から読み込む
+pub fn load_autosave() -> Result<ReversiState, ReversiError> {
+    fileio::read_autosave().and_then(restore)
+}
+
+/// ゲームを最後まで終えたので、自動保存を削除する
+pub fn discard_autosave() -> Result<(), ReversiError> {
+    fileio::delete_autosave()
+}
+
+/// カレントフォルダの以前の保存ファイルのファイル名。なければNone
+pub fn legacy_save_name() -> Option<&'static str> {
+    fileio::legacy_file_name()
+}
+
+/// カレントフォルダの以前の保存ファイルを読み込む
+pub fn load_legacy() -> Result<ReversiState, ReversiError> {
+    fileio::read_legacy_file().and_then(restore)
+}
+
+fn restore(save_data: SaveData) -> Result<ReversiState, ReversiError> {
+    // ファイル読み込みに成功していれば、開始局面から打ち直してゲーム状態を復元する
+    let mut state = replay_saved_moves(&save_data)?;
+    for _ in 0..save_data.redo_list.len() {
+        state.undo()?;
+    }
+
+    // 投了や時間切れは打ち直しても分からないので、保存した結果から戻す
+    if let Some(result) = &save_data.result {
+        if !state.gameover && matches!(result.end_reason, EndReason::Resignation(_) | EndReason::Timeout(_)) {
+            end_game(&mut state, result.end_reason);
+        }
+    }
+
+    state.computer = save_data.computer;
+    state.info = save_data.info;
+    Ok(state)
+}
+
+/// 保存されていた手をput_stoneと同じ確認をしながら打ち直す。
+/// 手番の順序と、置けないときのパスが記録どおりかも確かめる
+fn replay_saved_moves(save_data: &SaveData) -> Result<ReversiState, ReversiError> {
+    let mut state = start_from_position(save_data.initial_board, save_data.initial_turn, save_data.variant)?;
+    let moves: Vec<&Move> = save_data.undo_list.iter().chain(save_data.redo_list.iter()).collect();
+    for (i, mv) in moves.iter().enumerate() {
+        let location = save_data.move_locations.get(i).cloned().unwrap_or(Location::Move(i + 1));
+
+        // 置ける場所がなく自動でパスした手は、記録もパスであること
+        if let Some(auto_pass) = state.undo_buffer.get(i) {
+            if !mv.is_pass() {
+                return Err(ReversiError::MissingPass { turn: auto_pass.turn }.at(location));
+            }
+            if mv.turn != auto_pass.turn {
+                return Err(ReversiError::WrongTurn { expected: auto_pass.turn }.at(location));
+            }
+            copy_annotation(&mut state.undo_buffer[i], mv.eval, &mv.time);
+            continue;
+        }
+
+        if state.gameover {
+            return Err(ReversiError::GameOver.at(location));
+        }
+        if mv.turn != state.turn {
+            return Err(ReversiError::WrongTurn { expected: state.turn }.at(location));
+        }
+        let pos = match &mv.put_pos {
+            Some(pos) => pos,
+            None => return Err(ReversiError::IllegalPass { turn: state.turn }.at(location)),
+        };
+        put_stone(&mut state, pos.row as usize, pos.col as usize)
+            .map_err(|e| e.at(location))?;
+        copy_annotation(&mut state.undo_buffer[i], mv.eval, &mv.time);
+    }
+
+    // 最後の手の後に自動でパスした場合も、そのパスが記録されていること
+    if state.undo_buffer.len() > moves.len() {
+        return Err(ReversiError::MissingPass { turn: opposite_turn(state.turn) }.at(Location::EndOfFile));
+    }
+
+    Ok(state)
+}
+
+/// 読み込んだ手の評価値と消費時間を、打ち直した手に写す
+fn copy_annotation(mv: &mut Move, eval: Option<f64>, time: &Option<String>) {
+    mv.eval = eval;
+    mv.time = time.clone();
+}
+
+/// 棋譜の文字列(例: f5d6c3)から、初期局面から打ち直したゲーム状態を作る
+pub fn import_move_string(text: &str) -> Result<ReversiState, ReversiError> {
+    replay_moves(&move_list::parse_moves(text)?)
+}
+
+/// 棋譜ファイルから、初期局面から打ち直したゲーム状態を作る
+pub fn import_move_file(path: &str) -> Result<ReversiState, ReversiError> {
+    replay_moves(&move_list::read_file(path)?)
+}
+
+/// 戻した手も含めたゲーム全体の棋譜の文字列
+pub fn export_move_string(state: &ReversiState) -> Result<String, ReversiError> {
+    check_move_list_start(state)?;
+    Ok(move_list::format_moves(state.undo_buffer.iter().chain(state.redo_buffer.iter().rev())))
 }
 
+/// 戻した手も含めたゲーム全体の棋譜をファイルに書き出す
+pub fn export_move_file(state: &ReversiState, path: &str) -> Result<(), ReversiError> {
+    check_move_list_start(state)?;
+    move_list::write_file(path, state.undo_buffer.iter().chain(state.redo_buffer.iter().rev()))
+}
 
-pub fn save(state: &ReversiState) -> Result<(), ReversiError> {
-    match fileio::write_file(&state) {
-        Ok(_) => Ok(()),
-        Err(e) => Err(ReversiError::new(format!("保存に失敗しました: {:?}", e))),
+/// 棋譜は8x8の初期局面から黒の手番で打ち直して読み込むので、
+/// ハンデ戦や局面から始めたゲームなど、それ以外から始めたゲームは書き出せない
+fn check_move_list_start(state: &ReversiState) -> Result<(), ReversiError> {
+    if state.initial_board != Board::new() || state.initial_turn != Turn::Black {
+        return Err(ReversiError::NonStandardStart);
     }
+    Ok(())
 }
 
+/// 初期局面から順に石を置く。パスは自動で行う
+fn replay_moves(moves: &[CellPos]) -> Result<ReversiState, ReversiError> {
+    let mut state = ReversiState::new();
+    for (i, pos) in moves.iter().enumerate() {
+        put_stone(&mut state, pos.row as usize, pos.col as usize)
+            .map_err(|e| e.at(Location::Move(i + 1)))?;
+    }
+    Ok(state)
+}
 
-pub fn load() -> Result<ReversiState, ReversiError> {
-	// ファイル読み込みに成功していればゲーム状態を復元する。
-    fn restore_state(undo_list: Vec<Move>, redo_list: Vec<Move>) -> Result<ReversiState, Box<dyn std::error::Error>> {
-        fn do_one_move(state: &mut ReversiState, mv: &Move) -> Result<(), ReversiError> {
-            // 順番を決めて
-            state.turn = mv.turn;
+/// GGFファイルの最初のゲームを読み込み、開始局面から打ち直したゲーム状態を作る
+pub fn import_ggf_file(path: &str) -> Result<(GgfGame, ReversiState), ReversiError> {
+    let mut games = ggf::read_file(path)?;
+    let game = games.remove(0);
+    let mut state = replay_ggf(&game)?;
+    state.info.black_player = game.black_player.clone();
+    state.info.white_player = game.white_player.clone();
+    Ok((game, state))
+}
 
-            // 次に置く石を決めて
-            let stone = if mv.turn == Turn::Black { CellState::BlackStone } else { CellState::WhiteStone };
+/// 戻した手も含めたゲーム全体をGGFファイルに書き出す
+/// GGFには置けないセルを書けないので、置けないセルのある盤面のゲームは書き出せない
+pub fn export_ggf_file(state: &ReversiState, path: &str) -> Result<(), ReversiError> {
+    if state.initial_board.has_blocked_cell() {
+        return Err(ReversiError::BlockedCellsInGgf);
+    }
+    ggf::write_file(path, &to_ggf(state))
+}
 
-            // 石を置いて
-            state.board.set_cell(
-                mv.put_pos.row as usize, mv.put_pos.col as usize,
-                stone)?;
+/// GGFの開始局面から順に石を置く。
+/// 置ける場所がないときのパスは自動で行うので、GGFのパスはそれと一致すること(省略されていてもよい)
+fn replay_ggf(game: &GgfGame) -> Result<ReversiState, ReversiError> {
+    let mut state = start_from_position(game.initial_board, game.initial_turn, game.variant())?;
+    let mut replayed = 0;
+    for (i, mv) in game.moves.iter().enumerate() {
+        let location = Location::Move(i + 1);
+
+        // 自動で行ったパス
+        if replayed < state.undo_buffer.len() {
+            replayed += 1;
+            if mv.put_pos.is_none() {
+                let expected = state.undo_buffer[replayed - 1].turn;
+                if expected != mv.turn {
+                    return Err(ReversiError::WrongTurn { expected }.at(location));
+                }
+                copy_annotation(&mut state.undo_buffer[replayed - 1], mv.eval, &mv.time);
+                continue;
+            }
+        }
 
-            // ひっくり返す
-            let flip_cells = state.board.find_flippable_cells(
-                mv.put_pos.row as usize, mv.put_pos.col as usize, stone)?;
-            state.board.flip(&flip_cells);
+        let pos = match &mv.put_pos {
+            Some(pos) => pos,
+            None => return Err(ReversiError::IllegalPass { turn: state.turn }.at(location)),
+        };
+        if mv.turn != state.turn {
+            return Err(ReversiError::WrongTurn { expected: state.turn }.at(location));
+        }
+        put_stone(&mut state, pos.row as usize, pos.col as usize)
+            .map_err(|e| e.at(location))?;
+        copy_annotation(&mut state.undo_buffer[replayed], mv.eval, &mv.time);
+        replayed += 1;
+    }
 
-            // 手を記録する
-            state.register_move(
-                mv.put_pos.row as usize, mv.put_pos.col as usize, flip_cells);
+    // 投了や時間切れで終わったゲーム
+    if !state.gameover {
+        let loser = if game.result.starts_with('-') { Turn::Black } else { Turn::White };
+        if game.result.ends_with(":r") {
+            end_game(&mut state, EndReason::Resignation(loser));
+        } else if game.result.ends_with(":t") {
+            end_game(&mut state, EndReason::Timeout(loser));
+        }
+    }
 
-            switch_turn(state);
+    Ok(state)
+}
 
-            Ok(())
+/// ゲーム状態をGGFのゲームにする
+fn to_ggf(state: &ReversiState) -> GgfGame {
+    let player_name = |turn: Turn| {
+        let name = if turn == Turn::Black { &state.info.black_player } else { &state.info.white_player };
+        if !name.is_empty() {
+            return name.clone();
         }
+        match state.computer {
+            Some(computer) if computer.turn == turn => format!("computer-{}", computer.level),
+            _ => "player".to_string(),
+        }
+    };
+
+    let mut game = GgfGame::new();
+    game.place = "rust_reversi".to_string();
+    game.date = ggf::current_date();
+    game.black_player = player_name(Turn::Black);
+    game.white_player = player_name(Turn::White);
+    game.board_type = ggf::board_type(state.initial_board.size(), state.variant);
+    game.initial_board = state.initial_board;
+    game.initial_turn = state.initial_turn;
+    game.moves = state.undo_buffer.iter().chain(state.redo_buffer.iter().rev())
+        .map(|mv| GgfMove { turn: mv.turn, put_pos: mv.put_pos.clone(), eval: mv.eval, time: mv.time.clone() })
+        .collect();
+
+    // 結果は黒から見た石差。投了と時間切れは負けた側の石を全て相手のものとする
+    if let Some(result) = get_result(state) {
+        let disc_diff = result.black_count as i32 - result.white_count as i32;
+        let all = state.board.playable_cell_count() as i32;
+        game.result = match result.end_reason {
+            EndReason::Resignation(turn) => format!("{:+}.000:r", if turn == Turn::Black { -all } else { all }),
+            EndReason::Timeout(turn) => format!("{:+}.000:t", if turn == Turn::Black { -all } else { all }),
+            _ => format!("{:+}.000", disc_diff),
+        };
+    }
 
+    game
+}
+
+/// WTHORの.wtbファイルを読み込む。対局者名と大会名は同じフォルダの.jou/.trnファイルから引く
+pub fn open_wthor_database(path: &str) -> Result<WthorDatabase, ReversiError> {
+    let players = match wthor::find_companion_file(path, "jou") {
+        Some(jou_path) => wthor::read_players(&jou_path)?,
+        None => Vec::new(),
+    };
+    let tournaments = match wthor::find_companion_file(path, "trn") {
+        Some(trn_path) => wthor::read_tournaments(&trn_path)?,
+        None => Vec::new(),
+    };
+    wthor::read_games(path, &players, &tournaments)
+}
+
+/// WTHORの1局を初期局面から打ち直したゲーム状態を作る
+pub fn replay_wthor_game(game: &WthorGame) -> Result<ReversiState, ReversiError> {
+    let moves: Vec<CellPos> = game.moves.iter()
+        .filter_map(|mv| mv.put_pos.clone())
+        .collect();
+    replay_moves(&moves)
+}
+
+/// boardの局面、turnの手番から、variantのルールで始めるゲーム状態を作る。
+/// turn側が置けなければパスし、どちらも置けなければゲーム終了とする
+pub fn start_from_position(board: Board, turn: Turn, variant: Variant) -> Result<ReversiState, ReversiError> {
+    let mut state = ReversiState::with_variant(board, turn, variant);
+    if !can_put_stone(&state)? {
+        // next_turnは手番を切り替えてから判定するので、先に戻しておく
+        switch_turn(&mut state);
+        next_turn(&mut state)?;
+    }
+    Ok(state)
+}
+
+/// 1辺がboard_sizeセルの盤面の初期配置から、variantのルールで始めるゲーム状態を作る
+pub fn new_game(board_size: usize, variant: Variant) -> Result<ReversiState, ReversiError> {
+    start_from_position(Board::with_size(board_size)?, Turn::Black, variant)
+}
+
+/// 1辺がboard_sizeセルの盤面の初期配置に、handicapの隅の石を加えた局面から、
+/// variantのルールで始めるゲーム状態を作る。手番は黒から
+pub fn new_handicap_game(board_size: usize, variant: Variant, handicap: Handicap) -> Result<ReversiState, ReversiError> {
+    let mut board = Board::with_size(board_size)?;
+    handicap.apply(&mut board)?;
+    start_from_position(board, Turn::Black, variant)
+}
+
+/// 局面の文字列から、variantのルールで始めるゲーム状態を作る
+pub fn new_game_from_position(text: &str, variant: Variant) -> Result<ReversiState, ReversiError> {
+    let (board, turn) = position::parse_position(text)?;
+    validate_position(&board)?;
+    start_from_position(board, turn, variant)
+}
+
+/// レイアウトファイルの盤面から、variantのルールで始めるゲーム状態を作る。手番は黒から
+pub fn new_game_from_layout(path: &str, variant: Variant) -> Result<ReversiState, ReversiError> {
+    let board = load_layout(path)?;
+    validate_position(&board)?;
+    start_from_position(board, Turn::Black, variant)
+}
+
+/// レイアウトファイルを読み込み、置けないセルを含む盤面にする
+pub fn load_layout(path: &str) -> Result<Board, ReversiError> {
+    layout::read_file(path)
+}
+
+/// 局面の文字列から始めるゲーム状態を作る
+pub fn import_position(text: &str) -> Result<ReversiState, ReversiError> {
+    new_game_from_position(text, Variant::Standard)
+}
+
+/// 現在の局面の文字列
+pub fn export_position(state: &ReversiState) -> String {
+    position::format_position(&state.board, state.turn)
+}
+
+/// 局面の文字列を盤面と手番にする
+pub fn parse_position(text: &str) -> Result<(Board, Turn), ReversiError> {
+    position::parse_position(text)
+}
+
+/// 盤面と手番を局面の文字列にする
+pub fn format_position(board: &Board, turn: Turn) -> String {
+    position::format_position(board, turn)
+}
+
+/// boardの局面から対局できるか確かめる。どちらかが置ければよい
+pub fn validate_position(board: &Board) -> Result<(), ReversiError> {
+    if board.has_legal_move(CellState::BlackStone)? || board.has_legal_move(CellState::WhiteStone)? {
+        Ok(())
+    } else {
+        Err(ReversiError::NoLegalMoves)
+    }
+}
+
+/// 対局者名と開始日時がまだなければ設定する
+pub fn fill_game_info(state: &mut ReversiState, black_player: String, white_player: String) {
+    if state.info.black_player.is_empty() {
+        state.info.black_player = black_player;
+    }
+    if state.info.white_player.is_empty() {
+        state.info.white_player = white_player;
+    }
+    if state.info.started_at.is_empty() {
+        state.info.started_at = Timestamp::now().format_iso8601();
+    }
+}
+
+/// 設定ファイルを読み込む
+pub fn load_config() -> Result<Config, ReversiError> {
+    config::read_config()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain_model::computer_setting::ComputerSetting;
+    use crate::domain_model::game_info::GameInfo;
+    use crate::error::ParseErrorKind;
+
+    // 4x4の初期配置からこの順に打つと、最後の白の手の後で黒は置けずにパスする
+    const PASS_GAME: [(usize, usize); 4] = [(4, 3), (4, 2), (2, 1), (4, 4)];
+
+    fn play(state: &mut ReversiState, moves: &[(usize, usize)]) {
+        for &(row, col) in moves {
+            put_stone(state, row, col).unwrap();
+        }
+    }
+
+    fn pass_game() -> ReversiState {
+        let mut state = new_game(4, Variant::Standard).unwrap();
+        play(&mut state, &PASS_GAME);
+        state
+    }
+
+    fn board_after(moves: &[(usize, usize)]) -> Board {
+        let mut state = new_game(4, Variant::Standard).unwrap();
+        play(&mut state, moves);
+        state.board
+    }
+
+    #[test]
+    fn forced_pass_is_recorded_as_a_move_without_position() {
+        let state = pass_game();
+        assert_eq!(state.undo_buffer.len(), PASS_GAME.len() + 1);
+        let pass = state.undo_buffer.last().unwrap();
+        assert_eq!(pass.turn, Turn::Black);
+        assert!(pass.put_pos.is_none());
+        assert!(last_move_is_pass(&state));
+        assert_eq!(state.turn, Turn::White);
+        assert!(!state.gameover);
+    }
+
+    #[test]
+    fn game_ends_when_neither_side_can_move() {
+        // 黒がc1に置くと白の石がなくなり、どちらも置けない
+        let mut state = new_game_from_position("XO-- ---- ---- ---- X", Variant::Standard).unwrap();
+        put_stone(&mut state, 1, 3).unwrap();
+        assert!(state.gameover);
+        assert_eq!(state.end_reason, Some(EndReason::NoMoves));
+        assert_eq!(state.undo_buffer.len(), 1);
+        assert!(matches!(put_stone(&mut state, 2, 1), Err(ReversiError::GameOver)));
+
+        let result = get_result(&state).unwrap();
+        assert_eq!(result.outcome, Outcome::Win(Turn::Black));
+        assert_eq!((result.black_count, result.white_count, result.empty_count), (3, 0, 13));
+    }
+
+    #[test]
+    fn undo_over_a_pass_returns_to_the_previous_real_move() {
+        let mut state = pass_game();
+        undo(&mut state).unwrap();
+        assert_eq!(state.undo_buffer.len(), PASS_GAME.len() - 1);
+        assert_eq!(state.redo_buffer.len(), 2);
+        assert_eq!(state.turn, Turn::White);
+        assert_eq!(state.board, board_after(&PASS_GAME[..3]));
+    }
+
+    #[test]
+    fn redo_replays_the_move_and_its_pass() {
+        let mut state = pass_game();
+        let board = state.board;
+        undo(&mut state).unwrap();
+        redo(&mut state).unwrap();
+        assert_eq!(state.undo_buffer.len(), PASS_GAME.len() + 1);
+        assert!(state.redo_buffer.is_empty());
+        assert_eq!(state.turn, Turn::White);
+        assert_eq!(state.board, board);
+        assert!(matches!(redo(&mut state), Err(ReversiError::NothingToRedo)));
+    }
+
+    #[test]
+    fn jump_to_stops_after_a_following_pass() {
+        let mut state = pass_game();
+        jump_to(&mut state, 0).unwrap();
+        assert!(state.undo_buffer.is_empty());
+        assert_eq!(state.board, Board::with_size(4).unwrap());
+
+        // 4手目の直後は黒のパスが続くので、パスした後の局面になる
+        jump_to(&mut state, 4).unwrap();
+        assert_eq!(state.undo_buffer.len(), PASS_GAME.len() + 1);
+        assert_eq!(state.turn, Turn::White);
+
+        jump_to(&mut state, 3).unwrap();
+        assert_eq!(state.undo_buffer.len(), 3);
+        assert_eq!(state.turn, Turn::White);
+        assert_eq!(state.board, board_after(&PASS_GAME[..3]));
+        assert!(matches!(jump_to(&mut state, 6), Err(ReversiError::MoveNumberOutOfRange { max: 5 })));
+    }
+
+    #[test]
+    fn initial_pass_is_not_undone() {
+        // 黒は置けないので、開始してすぐにパスする
+        let mut state = new_game_from_position("OX-- ---- ---- ---- X", Variant::Standard).unwrap();
+        assert_eq!(state.undo_buffer.len(), 1);
+        assert!(last_move_is_pass(&state));
+        assert_eq!(state.turn, Turn::White);
+        assert!(matches!(undo(&mut state), Err(ReversiError::NothingToUndo)));
+
+        put_stone(&mut state, 1, 3).unwrap();
+        assert!(state.gameover);
+        undo(&mut state).unwrap();
+        assert_eq!(state.undo_buffer.len(), 1);
+        assert_eq!(state.turn, Turn::White);
+        assert!(!state.gameover);
+    }
+
+    #[test]
+    fn move_list_round_trips_through_export_and_import() {
         let mut state = ReversiState::new();
+        play(&mut state, &[(5, 6), (6, 4), (3, 3), (3, 4), (4, 3)]);
+        undo(&mut state).unwrap();
+        let text = export_move_string(&state).unwrap();
+        assert_eq!(text, "f5d6c3d3c4");
+
+        let imported = import_move_string(&text).unwrap();
+        assert_eq!(imported.undo_buffer.len(), 5);
+        assert_eq!(export_move_string(&imported).unwrap(), text);
+        redo(&mut state).unwrap();
+        assert_eq!(imported.board, state.board);
+        assert_eq!(imported.turn, state.turn);
+    }
 
-        for mv in &undo_list {
-            do_one_move(&mut state, &mv)?;
+    #[test]
+    fn move_list_outside_the_8x8_board_is_rejected() {
+        for (text, number, row, col) in [("f5p16", 2, 16, 16), ("a10", 1, 10, 1)] {
+            match import_move_string(text) {
+                Err(ReversiError::At { location, source }) => {
+                    assert_eq!(location, Location::Move(number), "{}", text);
+                    assert!(matches!(*source, ReversiError::OutOfRange { row: r, col: c } if (r, c) == (row, col)), "{}", text);
+                },
+                _ => panic!("{}", text),
+            }
         }
-        for mv in &redo_list {
-            do_one_move(&mut state, &mv)?;
+    }
+
+    #[test]
+    fn move_list_export_requires_the_standard_start() {
+        let mut state = new_game(10, Variant::Standard).unwrap();
+        play(&mut state, &[(4, 5)]);
+        assert!(matches!(export_move_string(&state), Err(ReversiError::NonStandardStart)));
+
+        // 8x8の初期配置でも、白から始めたゲームは棋譜で表せない
+        let white_first = start_from_position(Board::new(), Turn::White, Variant::Standard).unwrap();
+        assert!(matches!(export_move_string(&white_first), Err(ReversiError::NonStandardStart)));
+    }
+
+    #[test]
+    fn ggf_round_trips_through_to_ggf_and_parse_games() {
+        let mut state = pass_game();
+        put_stone(&mut state, 1, 2).unwrap();
+        undo(&mut state).unwrap();
+        let text = ggf::format_game(&to_ggf(&state));
+
+        let game = ggf::parse_games(&text).unwrap().remove(0);
+        assert_eq!(game.moves.len(), PASS_GAME.len() + 2);
+        assert!(game.moves[PASS_GAME.len()].put_pos.is_none());
+        let replayed = replay_ggf(&game).unwrap();
+        redo(&mut state).unwrap();
+        assert_eq!(replayed.board, state.board);
+        assert_eq!(replayed.undo_buffer.len(), state.undo_buffer.len());
+        assert_eq!(replayed.gameover, state.gameover);
+    }
+
+    #[test]
+    fn ggf_export_rejects_blocked_cells() {
+        let state = new_game_from_position("#--- -OX- -XO- ---- X", Variant::Standard).unwrap();
+        let path = std::env::temp_dir().join(format!("rust_reversi_blocked_{}.ggf", std::process::id()));
+        let path = path.to_str().unwrap();
+        assert!(matches!(export_ggf_file(&state, path), Err(ReversiError::BlockedCellsInGgf)));
+        assert!(!std::path::Path::new(path).exists());
+    }
+
+    fn saved_move(turn: Turn, put_pos: Option<(i8, i8)>) -> Move {
+        let put_pos = put_pos.map(|(row, col)| CellPos { row, col });
+        Move { turn, put_pos, flipped_pos_list: Vec::new(), eval: None, time: None }
+    }
+
+    // JSONの保存ファイルと同じく、各手の位置はundo、redoの何番目か
+    fn save_data(undo_list: Vec<Move>, redo_list: Vec<Move>) -> SaveData {
+        let move_locations = (0..undo_list.len()).map(|i| Location::Entry { list: "undo", index: i + 1 })
+            .chain((0..redo_list.len()).map(|i| Location::Entry { list: "redo", index: i + 1 }))
+            .collect();
+        SaveData {
+            info: GameInfo::default(),
+            computer: None,
+            initial_board: Board::with_size(4).unwrap(),
+            initial_turn: Turn::Black,
+            variant: Variant::Standard,
+            result: None,
+            undo_list,
+            redo_list,
+            move_locations,
         }
-        for _ in 0..redo_list.len() {
-            state.undo()?;
+    }
+
+    fn restore_error(save_data: SaveData) -> (Location, ReversiError) {
+        match restore(save_data) {
+            Err(ReversiError::At { location, source }) => (location, *source),
+            Err(e) => panic!("no location: {:?}", e),
+            Ok(_) => panic!("restored"),
         }
+    }
+
+    #[test]
+    fn saved_game_round_trips() {
+        let mut state = pass_game();
+        put_stone(&mut state, 1, 2).unwrap();
+        undo(&mut state).unwrap();
+        undo(&mut state).unwrap();
+        let mut data = save_data(state.undo_buffer.clone(), state.redo_buffer.iter().rev().cloned().collect());
+        data.computer = Some(ComputerSetting { turn: Turn::White, level: AiLevel::Strong });
+        data.info.black_player = "alpha".to_string();
+
+        let mut restored = restore(data).unwrap();
+        assert_eq!(restored.board, state.board);
+        assert_eq!(restored.turn, state.turn);
+        assert_eq!((restored.undo_buffer.len(), restored.redo_buffer.len()), (3, 3));
+        assert_eq!(restored.computer, Some(ComputerSetting { turn: Turn::White, level: AiLevel::Strong }));
+        assert_eq!(restored.info.black_player, "alpha");
+
+        while redo(&mut restored).is_ok() {}
+        while redo(&mut state).is_ok() {}
+        assert_eq!(restored.board, state.board);
+        assert_eq!(restored.undo_buffer.len(), PASS_GAME.len() + 2);
+    }
+
+    #[test]
+    fn missing_pass_is_reported_at_its_entry() {
+        let mut undo_list = pass_game().undo_buffer;
+        undo_list.pop();
+        undo_list.push(saved_move(Turn::White, Some((1, 2))));
+        let (location, source) = restore_error(save_data(undo_list, Vec::new()));
+        assert_eq!(location, Location::Entry { list: "undo", index: 5 });
+        assert!(matches!(source, ReversiError::MissingPass { turn: Turn::Black }));
+    }
+
+    #[test]
+    fn missing_final_pass_is_reported_at_the_end_of_file() {
+        let mut redo_list = pass_game().undo_buffer;
+        redo_list.pop();
+        let undo_list = redo_list.drain(..2).collect();
+        let (location, source) = restore_error(save_data(undo_list, redo_list));
+        assert_eq!(location, Location::EndOfFile);
+        assert!(matches!(source, ReversiError::MissingPass { turn: Turn::Black }));
+    }
+
+    #[test]
+    fn move_out_of_turn_is_reported_at_its_entry() {
+        let first = saved_move(Turn::White, Some((4, 3)));
+        let (location, source) = restore_error(save_data(vec![first], Vec::new()));
+        assert_eq!(location, Location::Entry { list: "undo", index: 1 });
+        assert!(matches!(source, ReversiError::WrongTurn { expected: Turn::Black }));
+
+        // 自動で行ったパスの手番も確かめる
+        let mut undo_list = pass_game().undo_buffer;
+        undo_list.last_mut().unwrap().turn = Turn::White;
+        let (location, source) = restore_error(save_data(undo_list, Vec::new()));
+        assert_eq!(location, Location::Entry { list: "undo", index: 5 });
+        assert!(matches!(source, ReversiError::WrongTurn { expected: Turn::Black }));
+    }
+
+    #[test]
+    fn pass_with_a_legal_move_is_reported_at_its_entry() {
+        let undo_list = vec![saved_move(Turn::Black, Some((4, 3)))];
+        let redo_list = vec![saved_move(Turn::White, Some((4, 2))), saved_move(Turn::Black, None)];
+        let (location, source) = restore_error(save_data(undo_list, redo_list));
+        assert_eq!(location, Location::Entry { list: "redo", index: 2 });
+        assert!(matches!(source, ReversiError::IllegalPass { turn: Turn::Black }));
+    }
 
-        Ok(state)
+    #[test]
+    fn move_after_the_game_end_is_reported_at_its_entry() {
+        let mut data = save_data(vec![saved_move(Turn::Black, Some((1, 3)))], vec![saved_move(Turn::White, Some((2, 1)))]);
+        data.initial_board = position::parse_position("XO-- ---- ---- ---- X").unwrap().0;
+        let (location, source) = restore_error(data);
+        assert_eq!(location, Location::Entry { list: "redo", index: 1 });
+        assert!(matches!(source, ReversiError::GameOver));
     }
 
-    fileio::read_file()
-        .and_then(|(undo_list, redo_list)| restore_state(undo_list, redo_list) )
-        .or_else(|e| Err( ReversiError::new(format!("{}", e) )))
+    #[test]
+    fn legacy_save_is_restored() {
+        let text = "COMPUTER WHITE STRONG\n3\nBLACK 5 6\nWHITE 6 4\nBLACK 3 3\nWHITE 3 4\n";
+        let mut state = restore(fileio::parse_legacy_txt(text.as_bytes()).unwrap()).unwrap();
+        assert_eq!((state.undo_buffer.len(), state.redo_buffer.len()), (3, 1));
+        assert_eq!(state.turn, Turn::White);
+        assert_eq!(state.computer, Some(ComputerSetting { turn: Turn::White, level: AiLevel::Strong }));
+        redo(&mut state).unwrap();
+        assert_eq!(export_move_string(&state).unwrap(), "f5d6c3d3");
+    }
 
+    #[test]
+    fn legacy_undo_count_too_large_is_reported_at_its_line() {
+        for (text, line) in [("5\nBLACK 5 6\n", 1), ("COMPUTER BLACK GREEDY\n5\nBLACK 5 6\n", 2)] {
+            match fileio::parse_legacy_txt(text.as_bytes()) {
+                Err(ReversiError::At { location, source }) => {
+                    assert_eq!(location, Location::Line(line), "{}", text);
+                    assert!(matches!(*source, ReversiError::Parse(ParseErrorKind::UndoCountTooLarge { count: 5, moves: 1 })));
+                },
+                _ => panic!("{}", text),
+            }
+        }
+    }
+
+    #[test]
+    fn legacy_moves_are_reported_at_their_lines() {
+        match fileio::parse_legacy_txt("1\nBLACK 5 6\nWHITE 9 4\n".as_bytes()) {
+            Err(ReversiError::At { location, source }) => {
+                assert_eq!(location, Location::Line(3));
+                assert!(matches!(*source, ReversiError::Parse(ParseErrorKind::InvalidMove(_))));
+            },
+            _ => panic!("parsed"),
+        }
+
+        let data = fileio::parse_legacy_txt("2\nBLACK 5 6\nBLACK 6 4\n".as_bytes()).unwrap();
+        let (location, source) = restore_error(data);
+        assert_eq!(location, Location::Line(3));
+        assert!(matches!(source, ReversiError::WrongTurn { expected: Turn::White }));
+
+        let data = fileio::parse_legacy_txt("COMPUTER BLACK GREEDY\n0\nBLACK 4 4\n".as_bytes()).unwrap();
+        let (location, source) = restore_error(data);
+        assert_eq!(location, Location::Line(3));
+        assert!(matches!(source, ReversiError::Occupied { row: 4, col: 4 }));
+    }
+
+    #[test]
+    fn layout_without_a_legal_first_move_is_rejected() {
+        // 石が離れていて、どちらも置けない
+        let board = layout::parse_layout("X---\n----\n----\n---O").unwrap();
+        assert!(matches!(validate_position(&board), Err(ReversiError::NoLegalMoves)));
+
+        let path = std::env::temp_dir().join(format!("rust_reversi_layout_{}.txt", std::process::id()));
+        std::fs::write(&path, "#--#\n-XO-\n-OX-\n#--#\n").unwrap();
+        let state = new_game_from_layout(path.to_str().unwrap(), Variant::Standard);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(state.unwrap().board.playable_cell_count(), 12);
+    }
 }
@@ -0,0 +1,147 @@
+use std::io;
+use regex::Regex;
+
+use rust_reversi::domain_model::cell_pos::CellPos;
+use rust_reversi::domain_model::reversi_state::ReversiState;
+use rust_reversi::player::{Player, PlayerAction};
+use crate::view::{game_view, view_util};
+use crate::view::error_message::error_message;
+use crate::view::messages::messages;
+use rust_reversi::service::reversi_service;
+
+/// 画面から操作する人
+pub struct ConsolePlayer {
+    regex_ync: Regex,
+    regex_jump: Regex,
+}
+
+impl ConsolePlayer {
+    pub fn new() -> ConsolePlayer {
+        ConsolePlayer {
+            regex_ync: Regex::new(r"[ync]").unwrap(),
+            regex_jump: Regex::new(r"^j\s*([0-9]+)$").unwrap(),
+        }
+    }
+
+    /// ゲーム終了時に保存するか確認する。ゲームを継続するならNone
+    fn confirm_quit(&self) -> Option<PlayerAction> {
+        println!("{}", messages().game_quit());
+        println!("{}", messages().save_prompt());
+        let mut y_n_c = String::new();
+        loop {
+            io::stdin().read_line(&mut y_n_c).unwrap();
+            if self.regex_ync.is_match(y_n_c.as_str().trim()) {
+                break;
+            } else {
+                println!("{}", messages().enter_ync());
+                y_n_c.clear();
+            }
+        }
+        match y_n_c.as_str().trim() {
+            "y" => Some(PlayerAction::Quit { save: true }),
+            "n" => Some(PlayerAction::Quit { save: false }),
+            _ => {
+                println!("{}", messages().game_continued());
+                None
+            },
+        }
+    }
+}
+
+impl Player for ConsolePlayer {
+    fn name(&self) -> String {
+        messages().human().to_string()
+    }
+
+    fn is_human(&self) -> bool {
+        true
+    }
+
+    fn ask_move(&mut self, state: &ReversiState) -> PlayerAction {
+        // メニュー表示。ゲームを終了する？
+        if state.gameover {
+            println!("{}", messages().review_help());
+        } else {
+            println!("{}", messages().move_example());
+            for line in messages().command_help() {
+                println!("{}", line);
+            }
+            game_view::show_hint(state);
+        }
+
+        // 入力ループ
+        loop {
+            let mut line = String::new();
+            io::stdin().read_line(&mut line).unwrap();
+
+            match line.trim() {
+                // 終盤の完全読み
+                "a" => {
+                    game_view::show_endgame_analysis(state);
+                    continue;
+                },
+                "u" => return PlayerAction::Undo,
+                "r" => return PlayerAction::Redo,
+                "h" => {
+                    game_view::show_history(state);
+                    continue;
+                },
+                "x" => {
+                    match reversi_service::export_move_string(state) {
+                        Ok(moves) => println!("{}", messages().move_string(&moves)),
+                        Err(e) => println!("{}", messages().export_failed(&error_message(&e))),
+                    }
+                    continue;
+                },
+                "p" => {
+                    println!("{}", messages().position_string(&reversi_service::export_position(state)));
+                    continue;
+                },
+                "resign" => return PlayerAction::Resign,
+                // ゲーム終了要求
+                "0" => match self.confirm_quit() {
+                    Some(action) => return action,
+                    None => continue,
+                },
+                _ => {},
+            }
+
+            // 棋譜をファイルに書き出す
+            if let Some(path) = line.trim().strip_prefix("x ") {
+                let path = path.trim();
+                let result = if view_util::is_ggf_path(path) {
+                    reversi_service::export_ggf_file(state, path)
+                } else {
+                    reversi_service::export_move_file(state, path)
+                };
+                match result {
+                    Ok(_) => println!("{}", messages().exported(path)),
+                    Err(e) => println!("{}", messages().export_failed(&error_message(&e))),
+                }
+                continue;
+            }
+
+            // 手数を指定して移動
+            if let Some(c) = self.regex_jump.captures(line.trim()) {
+                match c[1].parse() {
+                    Ok(move_number) => return PlayerAction::Jump(move_number),
+                    Err(_) => {
+                        println!("{}", messages().move_number_too_large());
+                        continue;
+                    }
+                }
+            }
+
+            // オセロのセル指定。10以上の段や列は"10-3"のように区切る
+            let (row, col) = match view_util::parse_cell(&line) {
+                Some(cell) => cell,
+                None => {
+                    println!("{}", messages().invalid_input());
+                    println!("{}", messages().move_example_short());
+                    continue;
+                }
+            };
+            return PlayerAction::Put(CellPos { row: row as i8, col: col as i8 });
+        }
+    }
+}
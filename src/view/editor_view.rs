@@ -0,0 +1,175 @@
+use std::io;
+
+use rust_reversi::ai::endgame::MAX_SOLVABLE_EMPTIES;
+use rust_reversi::domain_model::board::Board;
+use rust_reversi::domain_model::cell::CellState;
+use rust_reversi::domain_model::computer_setting::{AiLevel, ComputerSetting};
+use rust_reversi::domain_model::reversi_state::ReversiState;
+use rust_reversi::domain_model::turn::Turn;
+use rust_reversi::domain_model::variant::Variant;
+use rust_reversi::error::ReversiError;
+use rust_reversi::service::reversi_service;
+use crate::view::{game_view, title_view};
+use crate::view::view_util::{cell_name, parse_cell, show_header2};
+use crate::view::error_message::error_message;
+use crate::view::messages::messages;
+
+/// 盤面エディタ。石を置いて局面を作り、解析したり対局を始めたりする。
+/// ルールは最初に選び、解析と対局の両方に使う。
+/// 対局を始める場合はそのゲーム状態を、タイトルに戻る場合はNoneを返す
+pub fn edit() -> Option<ReversiState> {
+    let variant = title_view::select_variant();
+    show_header2(messages().editor_header());
+    let mut board = Board::new();
+    let mut turn = Turn::Black;
+
+    loop {
+        game_view::show_board(&board);
+        println!("{}", messages().editor_turn(turn));
+        if variant != Variant::Standard {
+            println!("{}", messages().rule(variant));
+        }
+        for line in messages().editor_help() {
+            println!("{}", line);
+        }
+
+        let input = read_line();
+        if input.is_empty() {
+            return None;
+        }
+        let (command, arg) = match input.split_once(char::is_whitespace) {
+            Some((command, arg)) => (command, arg.trim()),
+            None => (input.as_str(), ""),
+        };
+
+        match command {
+            "b" | "w" | "e" | "f" | "x" => match parse_cell(arg) {
+                Some((row, col)) => {
+                    if let Err(e) = edit_cell(&mut board, command, row, col) {
+                        println!("{}", error_message(&e));
+                    }
+                },
+                None => println!("{}", messages().editor_cell_example()),
+            },
+            "t" => turn = if turn == Turn::Black { Turn::White } else { Turn::Black },
+            "c" => clear(&mut board),
+            "s" => match Board::with_size(board.size()) {
+                Ok(standard) => board = standard,
+                Err(e) => println!("{}", error_message(&e)),
+            },
+            "n" => match arg.parse::<usize>() {
+                Ok(size) => match Board::with_size(size) {
+                    Ok(resized) => board = resized,
+                    Err(e) => println!("{}", error_message(&e)),
+                },
+                Err(_) => println!("{}", messages().invalid_input()),
+            },
+            "l" => match reversi_service::load_layout(arg) {
+                Ok(loaded) => board = loaded,
+                Err(e) => println!("{}", error_message(&e)),
+            },
+            "i" => match reversi_service::parse_position(arg) {
+                Ok((parsed, parsed_turn)) => {
+                    board = parsed;
+                    turn = parsed_turn;
+                },
+                Err(e) => println!("{}", error_message(&e)),
+            },
+            "p" => println!("{}", messages().position_string(&reversi_service::format_position(&board, turn))),
+            "v" => match reversi_service::validate_position(&board) {
+                Ok(_) => println!("{}", messages().editor_playable()),
+                Err(e) => println!("{}", error_message(&e)),
+            },
+            "a" => analyze(board, turn, variant),
+            "g" => match reversi_service::validate_position(&board) {
+                Ok(_) => match start_game(board, turn, variant) {
+                    Ok(state) => return Some(state),
+                    Err(e) => println!("{}", error_message(&e)),
+                },
+                Err(e) => println!("{}", error_message(&e)),
+            },
+            _ => println!("{}", messages().invalid_input()),
+        }
+    }
+}
+
+/// commandに応じて、セルに黒か白を置く、空きにする、石を反転する、置けないセルにする
+fn edit_cell(board: &mut Board, command: &str, row: usize, col: usize) -> Result<(), ReversiError> {
+    let cell_state = match command {
+        "b" => CellState::BlackStone,
+        "w" => CellState::WhiteStone,
+        "e" => CellState::BlankCell,
+        "x" => CellState::OuterCell,
+        _ => match board.get_cell(row, col) {
+            Some(CellState::BlackStone) => CellState::WhiteStone,
+            Some(CellState::WhiteStone) => CellState::BlackStone,
+            Some(_) if board.is_in_range(row, col) => {
+                println!("{}", messages().editor_no_stone(&cell_name(row, col)));
+                return Ok(());
+            },
+            _ => return Err(ReversiError::OutOfRange { row, col }),
+        },
+    };
+    board.set_cell(row, col, cell_state)
+}
+
+/// 全ての石を取り除く。置けないセルはそのまま残す
+fn clear(board: &mut Board) {
+    for row in 1..=board.size() {
+        for col in 1..=board.size() {
+            if board.get_cell(row, col) != Some(CellState::OuterCell) {
+                // 範囲内のセルを空きにするだけなので失敗しない
+                board.set_cell(row, col, CellState::BlankCell).unwrap();
+            }
+        }
+    }
+}
+
+/// 局面を探索して最善手を示し、空きマスが少なければ完全読みもする
+fn analyze(board: Board, turn: Turn, variant: Variant) {
+    let state = match reversi_service::validate_position(&board)
+        .and_then(|_| reversi_service::start_from_position(board, turn, variant)) {
+        Ok(state) => state,
+        Err(e) => {
+            println!("{}", error_message(&e));
+            return;
+        }
+    };
+
+    // 手番側が置けなければ、パスした後の手番の最善手になる
+    if let Some(pos) = reversi_service::suggest_move(&state, AiLevel::Maximum) {
+        println!("{}", messages().suggested_move(state.turn, &cell_name(pos.row as usize, pos.col as usize)));
+    }
+    if state.board.count_blank_cells() as u32 <= MAX_SOLVABLE_EMPTIES {
+        game_view::show_endgame_analysis(&state);
+    }
+}
+
+/// 対戦相手を選び、局面から始めるゲーム状態を作る
+fn start_game(board: Board, turn: Turn, variant: Variant) -> Result<ReversiState, ReversiError> {
+    show_header2(messages().opponent_header());
+    for item in messages().opponent_items() {
+        println!("{}", item);
+    }
+    let computer = loop {
+        match read_line().as_str() {
+            "1" => break None,
+            "2" => {
+                let turn = title_view::select_computer_turn();
+                let level = title_view::select_computer_level();
+                break Some(ComputerSetting { turn, level });
+            },
+            _ => println!("{}", messages().enter_1_or_2()),
+        }
+    };
+
+    let mut state = reversi_service::start_from_position(board, turn, variant)?;
+    state.computer = computer;
+    Ok(state)
+}
+
+fn read_line() -> String {
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).unwrap();
+    line.trim().to_string()
+}
@@ -0,0 +1,7 @@
+use rust_reversi::error::ReversiError;
+use crate::view::messages::messages;
+
+/// エラーを利用者向けのメッセージにする。位置の情報があれば前に付ける
+pub fn error_message(e: &ReversiError) -> String {
+    messages().error(e)
+}
@@ -1,129 +1,303 @@
 use std::io;
-use regex::Regex;
 use std::io::Write;
 
-use crate::domain_model::reversi_state::ReversiState;
-use crate::view::view_util;
-use crate::service::reversi_service;
-use crate::error::ReversiError;
-use crate::domain_model::turn::Turn;
+use rust_reversi::domain_model::board::Board;
+use rust_reversi::domain_model::reversi_state::ReversiState;
+use crate::view::{view_util, save_list_view};
+use crate::view::view_util::{cell_name, turn_name};
+use rust_reversi::service::reversi_service;
+use rust_reversi::domain_model::turn::Turn;
+use rust_reversi::domain_model::variant::Variant;
+use rust_reversi::error::ReversiError;
+use rust_reversi::domain_model::game_result::{GameResult, Outcome, EndReason};
+use rust_reversi::player::{Player, PlayerAction};
+use rust_reversi::data::ggf::GgfGame;
+use rust_reversi::domain_model::computer_setting::AiLevel;
+use rust_reversi::player::greedy_player::GreedyPlayer;
+use rust_reversi::player::random_player::RandomPlayer;
+use rust_reversi::player::search_player::SearchPlayer;
+use crate::view::console_player::ConsolePlayer;
+use crate::view::error_message::error_message;
+use crate::view::messages::messages;
 
-pub fn show(state: &mut ReversiState) { //} -> io::Result<usize> {
-    // state.initialize();
-    // show_state(state);
+pub fn show(state: &mut ReversiState) {
+    let mut black = create_player(state, Turn::Black);
+    let mut white = create_player(state, Turn::White);
+    reversi_service::fill_game_info(state, player_label(state, Turn::Black), player_label(state, Turn::White));
+    // 通常と違うルールなら知らせる
+    if state.variant != Variant::Standard {
+        println!("{}", messages().rule(state.variant));
+    }
+    play(state, black.as_mut(), white.as_mut());
+}
+
+/// 対局者名がないときに使う、turn側のプレーヤーの呼び名
+fn player_label(state: &ReversiState, turn: Turn) -> String {
+    match state.computer {
+        Some(computer) if computer.turn == turn => messages().computer(computer.level),
+        _ => messages().human().to_string(),
+    }
+}
+
+/// 保存されている設定から、turn側のプレーヤーを作る
+fn create_player(state: &ReversiState, turn: Turn) -> Box<dyn Player> {
+    match state.computer {
+        Some(computer) if computer.turn == turn => match computer.level {
+            AiLevel::Random => Box::new(RandomPlayer::new()),
+            AiLevel::Greedy => Box::new(GreedyPlayer::new()),
+            level => Box::new(SearchPlayer::new(level)),
+        },
+        _ => Box::new(ConsolePlayer::new()),
+    }
+}
+
+/// 2人のプレーヤーでゲームを進める
+pub fn play<'a>(state: &mut ReversiState, black: &mut (dyn Player + 'a), white: &mut (dyn Player + 'a)) {
+    let black_is_human = black.is_human();
+    let white_is_human = white.is_human();
+    let is_human = |turn: Turn| if turn == Turn::Black { black_is_human } else { white_is_human };
+    let mut game_end_notified = false;
+    let mut saved = false;
 
     // ゲームループ
-    // let mut in_game = true;
-    'game_loop: loop {
+    loop {
         show_state(state);
 
-        // メニュー表示。ゲームを終了する？
-        view_util::show_header2(format!("{}の番", if state.turn == Turn::Black {"黒"} else {"白"}).as_str());
-        println!("例)43[Enter] (4段目の3列目に石を置く)");
-        println!("または0でゲーム終了");
-
-        // 入力ループ
-        let mut row = 0usize;
-        let mut col = 0usize;
-        'input_loop: loop {
-            let mut line = String::new();
-            io::stdin().read_line(&mut line).unwrap();
-
-            // "0"(ゲーム終了要求)の処理
-            if line.trim() == "0" {
-                println!("ゲームを終了します");
-                println!("ゲーム内容を保存しますか？[(y)es/(n)o/(c)ancel]");
-                let mut y_n_c = String::new();
-                loop {
-                    io::stdin().read_line(&mut y_n_c).unwrap();
-                    let regex_ync = Regex::new(r"[ync]").unwrap();
-                    if regex_ync.is_match(y_n_c.as_str().trim()) {
-                        break;
-                    } else {
-                        println!("y n c のいずれかを入力してください");
-                        y_n_c.clear();
-                    }
-                }
-                match y_n_c.as_str().trim() {
-                    "y" => {
-                        match reversi_service::save(state) {
-                            Ok(_) => {
-                                println!("ゲーム内容を保存しました");
-                            },
-                            Err(e) => {
-                                println!("ゲームの保存に失敗しました: {}", e.message);
-                            }
-                        }
-                        break 'game_loop;
-                    },
-                    "n" => {
-                        println!("ゲームを終了します");
-                        break 'game_loop;
-                    },
-                    "c" => {
-                        println!("ゲームを継続します");
-                        continue 'game_loop;
-                    },
-                    _ => {}
-                }
+        let turn = state.turn;
+        let history_size = reversi_service::get_history_size(state);
+        let action = if let Some(result) = reversi_service::get_result(state) {
+            // 勝敗判定。勝ち負け、置くところがなくなった。
+            show_result(&result);
+            if !game_end_notified {
+                black.notify_game_end(state, &result);
+                white.notify_game_end(state, &result);
+                game_end_notified = true;
             }
 
-            // オセロのセル指定
-            let r = Regex::new(r"^([1-8])([1-8])$").unwrap();
-            if !r.is_match(line.trim()) {
-                println!("入力が不正です。");
-                println!("例)43[Enter]");
-                continue 'input_loop;
+            // ゲーム終了後は、人のプレーヤーが棋譜を振り返れる
+            let reviewer = if black_is_human {
+                &mut *black
+            } else if white_is_human {
+                &mut *white
+            } else {
+                break;
+            };
+            view_util::show_header2(messages().review_header());
+            reviewer.ask_move(state)
+        } else {
+            let player = if turn == Turn::Black { &mut *black } else { &mut *white };
+            if player.is_human() {
+                view_util::show_header2(&messages().turn_header(turn));
+            } else {
+                let name = if turn == Turn::Black { &state.info.black_player } else { &state.info.white_player };
+                let name = if name.is_empty() { player_label(state, turn) } else { name.clone() };
+                view_util::show_header2(&messages().turn_header_with_player(turn, &name));
             }
-            let c = r.captures(line.trim()).unwrap();
-            // [デバッグ] println!("c:{:#?}", c);
-            row = c[1].trim().parse().unwrap();
-            col = c[2].trim().parse().unwrap();
-            // [デバッグ] println!("row={} col={}", row, col);
-            break;
-        } // 入力ループ
-
-        // 現在のターンのプレーヤーが石を置く
-        match reversi_service::put_stone(state, row, col) {
-            Ok(_) => {},
-            Err(e) => {
-                println!("{}", e.message);
+            player.ask_move(state)
+        };
+
+        // 現在のターンのプレーヤーが行動する
+        let is_put = matches!(action, PlayerAction::Put(_));
+        let result = match action {
+            PlayerAction::Put(pos) => {
+                reversi_service::put_stone(state, pos.row as usize, pos.col as usize)
+                    .map(|_| println!("{}", messages().stone_placed(turn, pos.row as usize, pos.col as usize)))
+            },
+            PlayerAction::Resign => reversi_service::resign(state),
+            PlayerAction::Undo => {
+                reversi_service::undo(state).and_then(|_| undo_to_human_turn(state, &is_human))
+            },
+            PlayerAction::Redo => {
+                // コンピュータの手は続けて進め、人の番にする
+                reversi_service::redo(state).and_then(|_| {
+                    while !is_human(state.turn) && !state.redo_buffer.is_empty() {
+                        reversi_service::redo(state)?;
+                    }
+                    Ok(())
+                })
+            },
+            PlayerAction::Jump(move_number) => {
+                reversi_service::jump_to(state, move_number).and_then(|_| undo_to_human_turn(state, &is_human))
+            },
+            PlayerAction::Quit { save } => {
+                if save {
+                    let slot = match save_list_view::ask_slot_name(state) {
+                        Some(slot) => slot,
+                        None => continue,
+                    };
+                    match reversi_service::save(state, &slot) {
+                        Ok(_) => {
+                            println!("{}", messages().saved(&slot));
+                            saved = true;
+                        },
+                        Err(e) => println!("{}", messages().save_failed(&error_message(&e))),
+                    }
+                } else {
+                    println!("{}", messages().game_quit());
+                }
+                break;
+            },
+        };
+        if let Err(e) = result {
+            println!("{}", error_message(&e));
+            if state.gameover || is_human(turn) {
                 continue;
             }
+            // 人以外のプレーヤーは同じ手を繰り返しかねないので、不正な行動は投了とする
+            println!("{}", messages().illegal_action_forfeit(turn));
+            if let Err(e) = reversi_service::resign(state) {
+                println!("{}", error_message(&e));
+                break;
+            }
+        }
+
+        // 中断に備えて、1手ごとに自動保存する
+        if let Err(e) = reversi_service::autosave(state) {
+            println!("{}", messages().autosave_failed(&error_message(&e)));
+        }
+
+        // 打った手を相手に知らせる。置けずにパスした場合も知らせる
+        if is_put {
+            for mv in &state.undo_buffer[history_size..] {
+                if mv.is_pass() {
+                    println!("{}", messages().passed(mv.turn));
+                }
+                match mv.turn {
+                    Turn::Black => white.notify_opponent_move(state, mv),
+                    Turn::White => black.notify_opponent_move(state, mv),
+                }
+            }
         }
+    }
 
-        // 勝敗判定。勝ち負け、置くところがなくなった。
-        if state.gameover {
-            println!("{}", reversi_service::get_result_string(state));
-            // in_game = false;
-            break 'game_loop;
+    // 最後まで終えたか保存できたときだけ、中断したゲームとして残さない。
+    // 保存せずにやめたときは、自動保存から再開できるよう残す
+    if state.gameover || saved {
+        if let Err(e) = reversi_service::discard_autosave() {
+            println!("{}", messages().autosave_delete_failed(&error_message(&e)));
         }
-        
     }
 
     // Ok(0)
 }
 
+/// コンピュータの番なら手を戻し、人の番にする
+fn undo_to_human_turn(state: &mut ReversiState, is_human: &dyn Fn(Turn) -> bool) -> Result<(), ReversiError> {
+    while !state.gameover && !is_human(state.turn) && reversi_service::get_history_size(state) > 0 {
+        reversi_service::undo(state)?;
+    }
+    Ok(())
+}
+
+/// 石を置けるセルを表示する
+pub fn show_hint(state: &ReversiState) {
+    if let Ok(legal_moves) = reversi_service::get_legal_moves(state) {
+        let cells: Vec<String> = legal_moves.iter()
+            .map(|m| cell_name(m.put_pos.row as usize, m.put_pos.col as usize))
+            .collect();
+        println!("{}", messages().legal_moves(&cells.join(" ")));
+    }
+}
+
 pub fn show_state(state: &ReversiState) {
-    show_board(state);   
+    show_board(&state.board);
 }
 
-pub fn show_board(state: &ReversiState) {
-    println!("   1  2  3  4  5  6  7  8(列)");
-    println!("  +-----------------------+");
-    for row in 1..=8 {
-        print!("{} |", row);
-        for col in 1..=8 {
-            print!("{}", state.board.cells[row][col]);
+pub fn show_board(board: &Board) {
+    // 段の番号の幅。10段以上あれば2桁にそろえる
+    let size = board.size();
+    let width = size.to_string().len();
+    let margin = " ".repeat(width);
+
+    let col_numbers: String = (1..=size).map(|col| format!("{:>3}", col)).collect();
+    println!("{}{}{}", margin, col_numbers, messages().col_axis());
+    println!("{} +{}+", margin, "-".repeat(size * 3 - 1));
+    for row in 1..=size {
+        print!("{:>width$} |", row, width = width);
+        for col in 1..=size {
+            print!("{}", messages().stone(board.get_cell(row, col).unwrap()));
             io::stdout().flush().unwrap();
             print!("|");
         }
         println!();
-        println!("  +--+--+--+--+--+--+--+--+");
+        println!("{} +{}", margin, "--+".repeat(size));
     }
-    println!("(段)");
+    println!("{}", messages().row_axis());
 }
 
-pub fn show_history(state: &ReversiState) {
+/// ゲームの結果を表示する
+pub fn show_result(result: &GameResult) {
+    view_util::show_header2(messages().game_over_header());
+    match result.end_reason {
+        EndReason::BoardFull => println!("{}", messages().board_full()),
+        EndReason::NoMoves => println!("{}", messages().no_moves()),
+        EndReason::Resignation(turn) => println!("{}", messages().resigned(turn)),
+        EndReason::Timeout(turn) => println!("{}", messages().timed_out(turn)),
+    }
+    println!("{}", messages().disc_counts(result.black_count, result.white_count, result.empty_count));
+    if result.variant != Variant::Standard {
+        println!("{}", messages().rule(result.variant));
+    }
+    match result.outcome {
+        Outcome::Win(turn) => println!("{}", messages().winner(turn)),
+        Outcome::Draw => println!("{}", messages().draw()),
+    }
+}
 
-}
\ No newline at end of file
+/// 現在の局面の完全読みの結果を表示する
+pub fn show_endgame_analysis(state: &ReversiState) {
+    println!("{}", messages().analyzing());
+    let result = match reversi_service::analyze_endgame(state) {
+        Ok(result) => result,
+        Err(e) => {
+            println!("{}", error_message(&e));
+            return;
+        }
+    };
+
+    let black_diff = if result.turn == Turn::Black { result.disc_diff } else { -result.disc_diff };
+    if black_diff > 0 {
+        println!("{}", messages().perfect_play_win(Turn::Black, black_diff));
+    } else if black_diff < 0 {
+        println!("{}", messages().perfect_play_win(Turn::White, -black_diff));
+    } else {
+        println!("{}", messages().perfect_play_draw());
+    }
+    let line: Vec<String> = result.best_line.iter()
+        .map(|pos| match pos {
+            Some(pos) => cell_name(pos.row as usize, pos.col as usize),
+            None => messages().pass().to_string(),
+        })
+        .collect();
+    println!("{}", messages().best_line(&line.join(" ")));
+}
+
+/// 棋譜を表示する。戻した手も含め、現在の局面の位置に印を付ける
+pub fn show_history(state: &ReversiState) {
+    view_util::show_header2(messages().history_header());
+    let moves = state.undo_buffer.iter().chain(state.redo_buffer.iter().rev());
+    let current = |is_current: bool| if is_current { messages().history_current() } else { "" };
+    println!("{:>4}. {}{}", 0, messages().history_start(), current(state.undo_buffer.is_empty()));
+    for (i, mv) in moves.enumerate() {
+        let pos = match &mv.put_pos {
+            Some(pos) => cell_name(pos.row as usize, pos.col as usize),
+            None => messages().pass().to_string(),
+        };
+        println!("{:>4}. {} {}{}", i + 1, turn_name(mv.turn), pos, current(i + 1 == state.undo_buffer.len()));
+    }
+}
+/// GGFから読み込んだゲームの対局情報を表示する
+pub fn show_ggf_info(game: &GgfGame) {
+    let rating = |rating: Option<f64>| rating.map(|r| format!("({:.0})", r)).unwrap_or_default();
+    println!("{}", messages().ggf_players(&format!("{}{}", game.black_player, rating(game.black_rating)),
+        &format!("{}{}", game.white_player, rating(game.white_rating))));
+    if !game.place.is_empty() || !game.date.is_empty() {
+        println!("{}", messages().ggf_place_date(&game.place, &game.date));
+    }
+    if !game.time_control.is_empty() {
+        println!("{}", messages().ggf_time_control(&game.time_control));
+    }
+    if !game.result.is_empty() {
+        println!("{}", messages().ggf_result(&game.result));
+    }
+}
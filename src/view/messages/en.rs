@@ -0,0 +1,335 @@
+use rust_reversi::domain_model::board::{MIN_BOARD_SIZE, MAX_BOARD_SIZE};
+use rust_reversi::domain_model::cell::CellState;
+use rust_reversi::domain_model::computer_setting::AiLevel;
+use rust_reversi::domain_model::turn::Turn;
+use rust_reversi::domain_model::variant::Variant;
+use rust_reversi::error::{Location, ParseErrorKind, ReversiError};
+use crate::view::messages::Messages;
+use crate::view::view_util::cell_name;
+
+/// 英語のメッセージ
+pub struct English;
+
+impl Messages for English {
+    fn turn_name(&self, turn: Turn) -> &'static str {
+        match turn {
+            Turn::Black => "Black",
+            Turn::White => "White",
+        }
+    }
+
+    fn stone(&self, cell: CellState) -> &'static str {
+        match cell {
+            CellState::BlackStone => "X ",
+            CellState::WhiteStone => "O ",
+            CellState::OuterCell => "##",
+            _ => "  ",
+        }
+    }
+
+    fn level_name(&self, level: AiLevel) -> &'static str {
+        match level {
+            AiLevel::Random => "Random",
+            AiLevel::Greedy => "Greedy",
+            AiLevel::Beginner => "Beginner",
+            AiLevel::Intermediate => "Intermediate",
+            AiLevel::Strong => "Strong",
+            AiLevel::Maximum => "Maximum",
+        }
+    }
+
+    fn invalid_input(&self) -> &'static str { "Invalid input" }
+    fn enter_range(&self, max: usize) -> String { format!("Enter a number from 1 to {}", max) }
+    fn pass(&self) -> &'static str { "pass" }
+    fn human(&self) -> &'static str { "Human" }
+    fn computer(&self, level: AiLevel) -> String { format!("Computer: {}", self.level_name(level)) }
+
+    fn usage(&self) -> &'static str {
+        "Usage: rust_reversi [--lang ja|en] [--moves MOVES | --moves-file FILE | --position POSITION]"
+    }
+
+    fn unknown_lang(&self, code: &str) -> String { format!("Unsupported language: {}", code) }
+    fn config_failed(&self, error: &str) -> String { format!("Could not read the config file: {}", error) }
+
+    fn app_title(&self) -> &'static str { "Reversi" }
+    fn menu_header(&self) -> &'static str { "Menu" }
+
+    fn menu_items(&self) -> &'static [&'static str] {
+        &[
+            "1. New game",
+            "2. Saved games",
+            "3. Play against the computer",
+            "4. Import a game record",
+            "5. Import from a WTHOR database",
+            "6. Board editor",
+            "9. Quit",
+        ]
+    }
+
+    fn new_game_started(&self) -> &'static str { "Starting a new game" }
+    fn computer_game_started(&self) -> &'static str { "Starting a game against the computer" }
+    fn import_header(&self) -> &'static str { "Import a game record" }
+
+    fn import_prompt(&self) -> &'static str {
+        "Enter moves (e.g. f5d6c3), a game record file name (.ggf for GGF), \
+         or a position (one XO-# character per cell and the side to move)"
+    }
+
+    fn imported(&self) -> &'static str { "Game record imported" }
+    fn import_failed(&self, error: &str) -> String { format!("Could not import the game record: {}", error) }
+    fn app_quit(&self) -> &'static str { "Goodbye" }
+    fn interrupted_header(&self) -> &'static str { "Interrupted game" }
+    fn resume_prompt(&self) -> &'static str { "The previous game was interrupted. Resume it? [y/n]" }
+    fn enter_y_or_n(&self) -> &'static str { "Enter y or n" }
+    fn interrupted_discarded(&self) -> &'static str { "Discarded the interrupted game" }
+    fn resuming(&self) -> &'static str { "Resuming the interrupted game" }
+    fn resume_failed(&self, error: &str) -> String { format!("Could not load the interrupted game: {}", error) }
+
+    fn ask_player_name(&self, turn: Turn) -> String {
+        format!("Enter the name of the {} player (leave blank to skip)", self.turn_name(turn).to_lowercase())
+    }
+
+    fn ask_board_size(&self, min: usize, max: usize, default: usize) -> String {
+        format!("Enter the board size, an even number from {} to {} (leave blank for {})", min, max, default)
+    }
+
+    fn variant_header(&self) -> &'static str { "Rules (leave blank for 1)" }
+
+    fn variant_name(&self, variant: Variant) -> &'static str {
+        match variant {
+            Variant::Standard => "Standard (most discs wins)",
+            Variant::Anti => "Anti (fewest discs wins)",
+        }
+    }
+
+    fn rule(&self, variant: Variant) -> String { format!("Rules: {}", self.variant_name(variant)) }
+    fn start_header(&self) -> &'static str { "Starting position (leave blank for 1)" }
+
+    fn start_items(&self) -> &'static [&'static str] {
+        &["1. Standard", "2. Handicap (corner discs for the weaker player)", "3. Enter a position", "4. Load a board shape from a layout file"]
+    }
+
+    fn handicap_turn_header(&self) -> &'static str { "Side receiving the handicap" }
+
+    fn ask_handicap_corners(&self, max: usize) -> String {
+        format!("Enter the number of corner discs from 1 to {} (placed top-left, bottom-right, top-right, bottom-left)", max)
+    }
+
+    fn position_prompt(&self) -> &'static str { "Enter a position (one XO-# character per cell and the side to move)" }
+    fn layout_prompt(&self) -> &'static str { "Enter the path of a layout file (# marks a blocked cell)" }
+    fn start_failed(&self, error: &str) -> String { format!("Could not start the game: {}", error) }
+
+    fn editor_header(&self) -> &'static str { "Board editor" }
+
+    fn editor_help(&self) -> &'static [&'static str] {
+        &[
+            "b N:place black w N:place white e N:empty f N:flip x N:block (N is a cell such as 43 or 10-3)",
+            "t:switch the side to move c:remove all stones s:standard start n SIZE:change the board size",
+            "i POSITION:load a position string l PATH:load a layout file p:show the position v:check the position",
+            "g:play from this position a:analyse this position blank line:back to the title",
+        ]
+    }
+
+    fn editor_turn(&self, turn: Turn) -> String { format!("To move: {}", self.turn_name(turn)) }
+    fn editor_cell_example(&self) -> &'static str { "Specify a cell such as 43 or 10-3" }
+    fn editor_no_stone(&self, cell: &str) -> String { format!("There is no stone on {}", cell) }
+    fn editor_playable(&self) -> &'static str { "A game can be played from this position" }
+    fn editor_game_started(&self) -> &'static str { "Starting a game from the edited position" }
+    fn opponent_header(&self) -> &'static str { "Opponent" }
+    fn opponent_items(&self) -> &'static [&'static str] { &["1. Two players", "2. Play against the computer"] }
+
+    fn suggested_move(&self, turn: Turn, cell: &str) -> String {
+        format!("Best move for {} (search): {}", self.turn_name(turn), cell)
+    }
+
+    fn computer_turn_header(&self) -> &'static str { "Computer's colour" }
+    fn computer_turn_items(&self) -> &'static [&'static str] { &["1. Black (moves first)", "2. White (moves second)"] }
+    fn enter_1_or_2(&self) -> &'static str { "Enter 1 or 2" }
+    fn computer_level_header(&self) -> &'static str { "Computer's strength" }
+
+    fn turn_header(&self, turn: Turn) -> String { format!("{} to move", self.turn_name(turn)) }
+
+    fn turn_header_with_player(&self, turn: Turn, player: &str) -> String {
+        format!("{} to move ({})", self.turn_name(turn), player)
+    }
+
+    fn review_header(&self) -> &'static str { "Review" }
+
+    fn stone_placed(&self, turn: Turn, row: usize, col: usize) -> String {
+        format!("{} played {}", self.turn_name(turn), cell_name(row, col))
+    }
+
+    fn saved(&self, slot: &str) -> String { format!("Game saved to {}", slot) }
+    fn save_failed(&self, error: &str) -> String { format!("Could not save the game: {}", error) }
+    fn game_quit(&self) -> &'static str { "Ending the game" }
+    fn autosave_failed(&self, error: &str) -> String { format!("Autosave failed: {}", error) }
+    fn autosave_delete_failed(&self, error: &str) -> String { format!("Could not delete the autosave: {}", error) }
+    fn passed(&self, turn: Turn) -> String { format!("{} has no legal move and passes", self.turn_name(turn)) }
+    fn illegal_action_forfeit(&self, turn: Turn) -> String { format!("{} chose an illegal move and forfeits", self.turn_name(turn)) }
+    fn legal_moves(&self, cells: &str) -> String { format!("Legal moves: {}", cells) }
+    fn col_axis(&self) -> &'static str { "(col)" }
+    fn row_axis(&self) -> &'static str { "(row)" }
+    fn game_over_header(&self) -> &'static str { "Game over" }
+    fn board_full(&self) -> &'static str { "The board is full" }
+    fn no_moves(&self) -> &'static str { "Neither player can move" }
+    fn resigned(&self, turn: Turn) -> String { format!("{} resigned", self.turn_name(turn)) }
+    fn timed_out(&self, turn: Turn) -> String { format!("{} ran out of time", self.turn_name(turn)) }
+
+    fn disc_counts(&self, black: i16, white: i16, empty: i16) -> String {
+        format!("Black: {}  White: {}  Empty: {}", black, white, empty)
+    }
+
+    fn winner(&self, turn: Turn) -> String { format!("{} wins", self.turn_name(turn)) }
+    fn draw(&self) -> &'static str { "Draw" }
+    fn analyzing(&self) -> &'static str { "Solving the endgame..." }
+
+    fn perfect_play_win(&self, turn: Turn, disc_diff: i32) -> String {
+        format!("With perfect play {} wins by {}", self.turn_name(turn), disc_diff)
+    }
+
+    fn perfect_play_draw(&self) -> &'static str { "With perfect play the game is a draw" }
+    fn best_line(&self, line: &str) -> String { format!("Best line: {}", line) }
+    fn history_header(&self) -> &'static str { "Moves" }
+    fn history_start(&self) -> &'static str { "start" }
+    fn history_current(&self) -> &'static str { " <- current" }
+    fn ggf_players(&self, black: &str, white: &str) -> String { format!("Black: {}  White: {}", black, white) }
+    fn ggf_place_date(&self, place: &str, date: &str) -> String { format!("Place: {}  Date: {}", place, date) }
+    fn ggf_time_control(&self, time_control: &str) -> String { format!("Time control: {}", time_control) }
+    fn ggf_result(&self, result: &str) -> String { format!("Result: {}", result) }
+
+    fn save_prompt(&self) -> &'static str { "Save the game? [(y)es/(n)o/(c)ancel]" }
+    fn enter_ync(&self) -> &'static str { "Enter y, n or c" }
+    fn game_continued(&self) -> &'static str { "Continuing the game" }
+
+    fn review_help(&self) -> &'static str {
+        "u/r/j N/h to review the moves, x [file] to export them, p to show the position, 0 to end the game"
+    }
+
+    fn move_example(&self) -> &'static str { "e.g. 43[Enter] (place a stone on row 4, column 3; write 10-3 for rows or columns from 10)" }
+    fn move_example_short(&self) -> &'static str { "e.g. 43[Enter]" }
+
+    fn command_help(&self) -> &'static [&'static str] {
+        &[
+            "u:undo r:redo j N:go to move N h:moves x [file]:export moves p:show position",
+            "a to solve the endgame, resign to resign, 0 to end the game",
+        ]
+    }
+
+    fn move_string(&self, moves: &str) -> String { format!("Moves: {}", moves) }
+    fn position_string(&self, position: &str) -> String { format!("Position: {}", position) }
+    fn exported(&self, path: &str) -> String { format!("Exported the moves to {}", path) }
+    fn export_failed(&self, error: &str) -> String { format!("Could not export the moves: {}", error) }
+    fn move_number_too_large(&self) -> &'static str { "Move number is too large" }
+
+    fn saves_header(&self) -> &'static str { "Saved games" }
+    fn saves_load_failed(&self, error: &str) -> String { format!("Could not list the saved games: {}", error) }
+    fn saves_help(&self) -> &'static str { "Number to load, d N:delete, r N NAME:rename, blank line to go back" }
+    fn no_saves(&self) -> &'static str { "No saved games" }
+    fn legacy_save(&self, file_name: &str) -> String { format!("(old save file) {}", file_name) }
+
+    fn slot_summary(&self, name: &str, saved_at: &str, black: &str, white: &str, move_count: usize) -> String {
+        format!("{}  {}  Black: {} White: {}  {} moves", name, saved_at, black, white, move_count)
+    }
+
+    fn unreadable_slot(&self, name: &str) -> String { format!("{}  (unreadable)", name) }
+    fn loaded(&self) -> &'static str { "Game loaded" }
+    fn load_failed(&self, error: &str) -> String { format!("Could not load the game: {}", error) }
+    fn confirm_delete(&self, name: &str) -> String { format!("Delete {}? [y/n]", name) }
+    fn deleted(&self) -> &'static str { "Deleted" }
+    fn delete_failed(&self, error: &str) -> String { format!("Could not delete: {}", error) }
+    fn renamed(&self) -> &'static str { "Renamed" }
+    fn rename_failed(&self, error: &str) -> String { format!("Could not rename: {}", error) }
+
+    fn ask_slot_name(&self, default_name: &str) -> String {
+        format!("Enter a save name (blank for \"{}\", c to cancel)", default_name)
+    }
+
+    fn confirm_overwrite(&self, name: &str) -> String { format!("{} already exists. Overwrite it? [y/n]", name) }
+
+    fn wthor_header(&self) -> &'static str { "Import from a WTHOR database" }
+    fn wthor_prompt(&self) -> &'static str { "Enter the name of a .wtb file" }
+    fn wthor_open_failed(&self, error: &str) -> String { format!("Could not read the WTHOR file: {}", error) }
+    fn wthor_summary(&self, year: u16, count: usize) -> String { format!("{} games from {}", count, year) }
+    fn wthor_broken(&self, count: usize) -> String { format!("{} games are broken", count) }
+    fn wthor_help(&self) -> &'static str { "Game number to select, part of a player name to search, blank line to go back" }
+    fn wthor_loaded(&self, n: usize, title: &str) -> String { format!("Loaded game {}: {}", n, title) }
+
+    fn wthor_game_title(&self, tournament: &str, black: &str, white: &str, black_score: u8, white_score: i32) -> String {
+        format!("{} Black: {} White: {} ({}-{})", tournament, black, white, black_score, white_score)
+    }
+
+    fn wthor_not_found(&self) -> &'static str { "No games found" }
+    fn wthor_more(&self, count: usize) -> String { format!("and {} more", count) }
+
+    fn error(&self, e: &ReversiError) -> String {
+        match e {
+            ReversiError::OutOfRange { row, col } => format!("{} is off the board", cell_name(*row, *col)),
+            ReversiError::Occupied { row, col } => format!("{} is already occupied", cell_name(*row, *col)),
+            ReversiError::Blocked { row, col } => format!("{} is a blocked cell", cell_name(*row, *col)),
+            ReversiError::NoFlips { row, col } => format!("Placing at {} flips no stones", cell_name(*row, *col)),
+            ReversiError::NotAStone(_) => "Not a stone".to_string(),
+            ReversiError::GameOver => "The game is over".to_string(),
+            ReversiError::WrongTurn { expected } => format!("It should be {}'s turn", self.turn_name(*expected)),
+            ReversiError::IllegalPass { turn } => format!("{} passed although a move is available", self.turn_name(*turn)),
+            ReversiError::MissingPass { turn } => format!("{} has no legal move and should pass", self.turn_name(*turn)),
+            ReversiError::NothingToUndo => "There is no move to undo".to_string(),
+            ReversiError::NothingToRedo => "There is no move to redo".to_string(),
+            ReversiError::MoveNumberOutOfRange { max } => format!("Enter a move number from 0 to {}", max),
+            ReversiError::TooManyEmpties { empties, max } =>
+                format!("There are {} empty cells. The endgame can be solved with {} or fewer", empties, max),
+            ReversiError::InvalidBoardSize(size) =>
+                format!("The board size must be an even number from {} to {}: {}", MIN_BOARD_SIZE, MAX_BOARD_SIZE, size),
+            ReversiError::InvalidHandicap { max } => format!("Enter a handicap from 1 to {} corners", max),
+            ReversiError::NoLegalMoves => "Neither side has a legal move".to_string(),
+            ReversiError::NonStandardStart =>
+                "Only games started from the standard 8x8 position can be written as a move list. Export to GGF (.ggf) instead".to_string(),
+            ReversiError::BlockedCellsInGgf =>
+                "Boards with blocked cells cannot be exported to GGF. Use the position string (p) instead".to_string(),
+            ReversiError::EmptySlotName => "Enter a save name".to_string(),
+            ReversiError::SlotNameTooLong { max } => format!("Save names can be at most {} characters", max),
+            ReversiError::InvalidSlotName(name) => format!("The save name contains invalid characters: {}", name),
+            ReversiError::SlotNotFound(name) => format!("No such save: {}", name),
+            ReversiError::SlotExists(name) => format!("A save with this name already exists: {}", name),
+            ReversiError::Io(e) => format!("File error: {}", e),
+            ReversiError::Json(e) => format!("Malformed JSON: {}", e),
+            ReversiError::Parse(kind) => self.parse_error(kind),
+            ReversiError::At { location, source } => format!("{}: {}", self.location(location), self.error(source)),
+        }
+    }
+
+    fn parse_error(&self, kind: &ParseErrorKind) -> String {
+        match kind {
+            ParseErrorKind::InvalidMove(s) => format!("Invalid move: {}", s),
+            ParseErrorKind::InvalidCell(c) => format!("Invalid board character: {}", c),
+            ParseErrorKind::InvalidTurn(s) => format!("Invalid side to move: {}", s),
+            ParseErrorKind::InvalidNumber(s) => format!("Invalid number: {}", s),
+            ParseErrorKind::InvalidValue { field, value } => format!("Invalid {}: {}", field, value),
+            ParseErrorKind::InvalidLength { expected, actual } =>
+                format!("Expected {} characters but found {}", expected, actual),
+            ParseErrorKind::Truncated => "The file is truncated".to_string(),
+            ParseErrorKind::MissingRecords { expected, actual } =>
+                format!("Expected {} records but found only {}", expected, actual),
+            ParseErrorKind::UndoCountTooLarge { count, moves } =>
+                format!("The move count is {} but there are only {} moves", count, moves),
+            ParseErrorKind::UnclosedGame => "The game is not closed".to_string(),
+            ParseErrorKind::NoGame => "No game found".to_string(),
+            ParseErrorKind::MalformedProperty(s) => format!("Malformed property: {}", s),
+            ParseErrorKind::NotOthello(s) => format!("Not an Othello game: {}", s),
+            ParseErrorKind::UnsupportedBoardSize(s) => format!("Unsupported board size: {}", s),
+            ParseErrorKind::UnsupportedVersion(version) =>
+                format!("Save files of the newer format (version {}) are not supported", version),
+            ParseErrorKind::UnsupportedVariant(s) => format!("Unsupported rule variant: {}", s),
+        }
+    }
+
+    fn location(&self, location: &Location) -> String {
+        match location {
+            Location::Line(line) => format!("line {}", line),
+            Location::Move(n) => format!("move {}", n),
+            Location::Game(n) => format!("game {}", n),
+            Location::Entry { list, index } => format!("{} entry {}", list, index),
+            Location::Cell { row, col } => format!("row {} column {}", row, col),
+            Location::EndOfFile => "end of file".to_string(),
+        }
+    }
+}
@@ -0,0 +1,336 @@
+use rust_reversi::domain_model::board::{MIN_BOARD_SIZE, MAX_BOARD_SIZE};
+use rust_reversi::domain_model::cell::CellState;
+use rust_reversi::domain_model::computer_setting::AiLevel;
+use rust_reversi::domain_model::turn::Turn;
+use rust_reversi::domain_model::variant::Variant;
+use rust_reversi::error::{Location, ParseErrorKind, ReversiError};
+use crate::view::messages::Messages;
+use crate::view::view_util::cell_name;
+
+/// 日本語のメッセージ
+pub struct Japanese;
+
+impl Messages for Japanese {
+    fn turn_name(&self, turn: Turn) -> &'static str {
+        match turn {
+            Turn::Black => "黒",
+            Turn::White => "白",
+        }
+    }
+
+    fn stone(&self, cell: CellState) -> &'static str {
+        match cell {
+            CellState::BlackStone => "黒",
+            CellState::WhiteStone => "白",
+            CellState::OuterCell => "##",
+            _ => "  ",
+        }
+    }
+
+    fn level_name(&self, level: AiLevel) -> &'static str {
+        match level {
+            AiLevel::Random => "ランダム",
+            AiLevel::Greedy => "貪欲",
+            AiLevel::Beginner => "初級",
+            AiLevel::Intermediate => "中級",
+            AiLevel::Strong => "上級",
+            AiLevel::Maximum => "最強",
+        }
+    }
+
+    fn invalid_input(&self) -> &'static str { "入力が不正です" }
+    fn enter_range(&self, max: usize) -> String { format!("1～{}を入力してください", max) }
+    fn pass(&self) -> &'static str { "パス" }
+    fn human(&self) -> &'static str { "人" }
+    fn computer(&self, level: AiLevel) -> String { format!("コンピュータ: {}", self.level_name(level)) }
+
+    fn usage(&self) -> &'static str {
+        "使い方: rust_reversi [--lang ja|en] [--moves 棋譜 | --moves-file 棋譜ファイル | --position 局面]"
+    }
+
+    fn unknown_lang(&self, code: &str) -> String { format!("対応していない言語です: {}", code) }
+    fn config_failed(&self, error: &str) -> String { format!("設定ファイルを読み込めませんでした: {}", error) }
+
+    fn app_title(&self) -> &'static str { "リバーシ" }
+    fn menu_header(&self) -> &'static str { "メニュー" }
+
+    fn menu_items(&self) -> &'static [&'static str] {
+        &[
+            "1. 新規ゲーム開始",
+            "2. 保存したゲーム",
+            "3. コンピュータと対戦",
+            "4. 棋譜を読み込む",
+            "5. WTHORデータベースから読み込む",
+            "6. 盤面エディタ",
+            "9. 終了",
+        ]
+    }
+
+    fn new_game_started(&self) -> &'static str { "新規ゲームを開始します" }
+    fn computer_game_started(&self) -> &'static str { "コンピュータとの対戦を開始します" }
+    fn import_header(&self) -> &'static str { "棋譜の読み込み" }
+
+    fn import_prompt(&self) -> &'static str {
+        "棋譜(例: f5d6c3)、棋譜ファイル名(.ggfならGGF形式)、または局面(XO-#でマス数分の文字と手番)を入力してください"
+    }
+
+    fn imported(&self) -> &'static str { "棋譜を読み込みました" }
+    fn import_failed(&self, error: &str) -> String { format!("棋譜を読み込めませんでした: {}", error) }
+    fn app_quit(&self) -> &'static str { "アプリを終了します" }
+    fn interrupted_header(&self) -> &'static str { "中断したゲーム" }
+    fn resume_prompt(&self) -> &'static str { "前回のゲームが途中で終了しています。再開しますか？[y/n]" }
+    fn enter_y_or_n(&self) -> &'static str { "yかnを入力してください" }
+    fn interrupted_discarded(&self) -> &'static str { "中断したゲームを破棄しました" }
+    fn resuming(&self) -> &'static str { "中断したゲームを再開します" }
+    fn resume_failed(&self, error: &str) -> String { format!("中断したゲームを読み込めませんでした: {}", error) }
+
+    fn ask_player_name(&self, turn: Turn) -> String {
+        format!("{}のプレーヤー名を入力してください(空欄なら省略)", self.turn_name(turn))
+    }
+
+    fn ask_board_size(&self, min: usize, max: usize, default: usize) -> String {
+        format!("盤面の1辺のマス数を{}～{}の偶数で入力してください(空欄なら{})", min, max, default)
+    }
+
+    fn variant_header(&self) -> &'static str { "ルール(空欄なら1)" }
+
+    fn variant_name(&self, variant: Variant) -> &'static str {
+        match variant {
+            Variant::Standard => "通常(石の多い方が勝ち)",
+            Variant::Anti => "アンチ(石の少ない方が勝ち)",
+        }
+    }
+
+    fn rule(&self, variant: Variant) -> String { format!("ルール: {}", self.variant_name(variant)) }
+    fn start_header(&self) -> &'static str { "開始局面(空欄なら1)" }
+
+    fn start_items(&self) -> &'static [&'static str] {
+        &["1. 標準の初期配置", "2. ハンデ戦(下手の隅に石を置く)", "3. 局面を入力する", "4. 盤面の形をファイルから読み込む"]
+    }
+
+    fn handicap_turn_header(&self) -> &'static str { "ハンデを受ける色" }
+
+    fn ask_handicap_corners(&self, max: usize) -> String {
+        format!("隅に置く石の数を1～{}で入力してください(左上、右下、右上、左下の順に置きます)", max)
+    }
+
+    fn position_prompt(&self) -> &'static str { "局面(XO-#でマス数分の文字と手番)を入力してください" }
+    fn layout_prompt(&self) -> &'static str { "レイアウトファイルのパスを入力してください(#が置けないセル)" }
+    fn start_failed(&self, error: &str) -> String { format!("ゲームを開始できませんでした: {}", error) }
+
+    fn editor_header(&self) -> &'static str { "盤面エディタ" }
+
+    fn editor_help(&self) -> &'static [&'static str] {
+        &[
+            "b N:黒を置く w N:白を置く e N:空きにする f N:石を反転する x N:置けないセルにする (Nは43や10-3のようなセル)",
+            "t:手番の切り替え c:石を全て取り除く s:初期配置に戻す n サイズ:盤面の大きさを変える",
+            "i 局面:局面の文字列を読み込む l パス:レイアウトファイルを読み込む p:局面の表示 v:局面の確認",
+            "g:この局面から対局 a:この局面を解析 空行:タイトルに戻る",
+        ]
+    }
+
+    fn editor_turn(&self, turn: Turn) -> String { format!("手番: {}", self.turn_name(turn)) }
+    fn editor_cell_example(&self) -> &'static str { "セルは43や10-3のように指定してください" }
+    fn editor_no_stone(&self, cell: &str) -> String { format!("{}には石がありません", cell) }
+    fn editor_playable(&self) -> &'static str { "この局面から対局できます" }
+    fn editor_game_started(&self) -> &'static str { "編集した局面から対局を開始します" }
+    fn opponent_header(&self) -> &'static str { "対戦相手" }
+    fn opponent_items(&self) -> &'static [&'static str] { &["1. 2人で対戦", "2. コンピュータと対戦"] }
+
+    fn suggested_move(&self, turn: Turn, cell: &str) -> String {
+        format!("{}の最善手(探索): {}", self.turn_name(turn), cell)
+    }
+
+    fn computer_turn_header(&self) -> &'static str { "コンピュータの色" }
+    fn computer_turn_items(&self) -> &'static [&'static str] { &["1. 黒(先手)", "2. 白(後手)"] }
+    fn enter_1_or_2(&self) -> &'static str { "1か2を入力してください" }
+    fn computer_level_header(&self) -> &'static str { "コンピュータの強さ" }
+
+    fn turn_header(&self, turn: Turn) -> String { format!("{}の番", self.turn_name(turn)) }
+
+    fn turn_header_with_player(&self, turn: Turn, player: &str) -> String {
+        format!("{}の番({})", self.turn_name(turn), player)
+    }
+
+    fn review_header(&self) -> &'static str { "振り返り" }
+
+    fn stone_placed(&self, turn: Turn, row: usize, col: usize) -> String {
+        format!("{}は{}に置きました", self.turn_name(turn), cell_name(row, col))
+    }
+
+    fn saved(&self, slot: &str) -> String { format!("ゲーム内容を{}に保存しました", slot) }
+    fn save_failed(&self, error: &str) -> String { format!("ゲームの保存に失敗しました: {}", error) }
+    fn game_quit(&self) -> &'static str { "ゲームを終了します" }
+    fn autosave_failed(&self, error: &str) -> String { format!("自動保存に失敗しました: {}", error) }
+    fn autosave_delete_failed(&self, error: &str) -> String { format!("自動保存の削除に失敗しました: {}", error) }
+    fn passed(&self, turn: Turn) -> String { format!("{}は置ける場所がないのでパスします", self.turn_name(turn)) }
+    fn illegal_action_forfeit(&self, turn: Turn) -> String { format!("{}が不正な手を選んだので投了とします", self.turn_name(turn)) }
+    fn legal_moves(&self, cells: &str) -> String { format!("置ける場所: {}", cells) }
+    fn col_axis(&self) -> &'static str { "(列)" }
+    fn row_axis(&self) -> &'static str { "(段)" }
+    fn game_over_header(&self) -> &'static str { "ゲーム終了" }
+    fn board_full(&self) -> &'static str { "盤面が全て埋まりました" }
+    fn no_moves(&self) -> &'static str { "両者とも置ける場所がなくなりました" }
+    fn resigned(&self, turn: Turn) -> String { format!("{}が投了しました", self.turn_name(turn)) }
+    fn timed_out(&self, turn: Turn) -> String { format!("{}が時間切れになりました", self.turn_name(turn)) }
+
+    fn disc_counts(&self, black: i16, white: i16, empty: i16) -> String {
+        format!("黒: {}  白: {}  空き: {}", black, white, empty)
+    }
+
+    fn winner(&self, turn: Turn) -> String { format!("{}の勝ち", self.turn_name(turn)) }
+    fn draw(&self) -> &'static str { "引き分け" }
+    fn analyzing(&self) -> &'static str { "完全読みしています..." }
+
+    fn perfect_play_win(&self, turn: Turn, disc_diff: i32) -> String {
+        format!("最善を尽くすと{}の{}石勝ち", self.turn_name(turn), disc_diff)
+    }
+
+    fn perfect_play_draw(&self) -> &'static str { "最善を尽くすと引き分け" }
+    fn best_line(&self, line: &str) -> String { format!("最善進行: {}", line) }
+    fn history_header(&self) -> &'static str { "棋譜" }
+    fn history_start(&self) -> &'static str { "開始" }
+    fn history_current(&self) -> &'static str { " <- 現在" }
+    fn ggf_players(&self, black: &str, white: &str) -> String { format!("黒: {}  白: {}", black, white) }
+    fn ggf_place_date(&self, place: &str, date: &str) -> String { format!("場所: {}  日時: {}", place, date) }
+    fn ggf_time_control(&self, time_control: &str) -> String { format!("持ち時間: {}", time_control) }
+    fn ggf_result(&self, result: &str) -> String { format!("結果: {}", result) }
+
+    fn save_prompt(&self) -> &'static str { "ゲーム内容を保存しますか？[(y)es/(n)o/(c)ancel]" }
+    fn enter_ync(&self) -> &'static str { "y n c のいずれかを入力してください" }
+    fn game_continued(&self) -> &'static str { "ゲームを継続します" }
+
+    fn review_help(&self) -> &'static str {
+        "u/r/j N/hで棋譜を振り返る、x [ファイル名]で棋譜の書き出し、pで局面の表示、0でゲーム終了"
+    }
+
+    fn move_example(&self) -> &'static str { "例)43[Enter] (4段目の3列目に石を置く。10以上は10-3のように区切る)" }
+    fn move_example_short(&self) -> &'static str { "例)43[Enter]" }
+
+    fn command_help(&self) -> &'static [&'static str] {
+        &[
+            "u:1手戻す r:1手進める j N:N手目に移動 h:棋譜 x [ファイル名]:棋譜の書き出し p:局面の表示",
+            "aで終盤の完全読み、resignで投了、0でゲーム終了",
+        ]
+    }
+
+    fn move_string(&self, moves: &str) -> String { format!("棋譜: {}", moves) }
+    fn position_string(&self, position: &str) -> String { format!("局面: {}", position) }
+    fn exported(&self, path: &str) -> String { format!("棋譜を書き出しました: {}", path) }
+    fn export_failed(&self, error: &str) -> String { format!("棋譜の書き出しに失敗しました: {}", error) }
+    fn move_number_too_large(&self) -> &'static str { "手数が大きすぎます" }
+
+    fn saves_header(&self) -> &'static str { "保存したゲーム" }
+    fn saves_load_failed(&self, error: &str) -> String { format!("保存したゲームの一覧を読み込めません: {}", error) }
+    fn saves_help(&self) -> &'static str { "番号で読み込み、d N:削除、r N 名前:名前の変更、空行で戻る" }
+    fn no_saves(&self) -> &'static str { "保存したゲームはありません" }
+    fn legacy_save(&self, file_name: &str) -> String { format!("(以前の保存ファイル) {}", file_name) }
+
+    fn slot_summary(&self, name: &str, saved_at: &str, black: &str, white: &str, move_count: usize) -> String {
+        format!("{}  {}  黒: {} 白: {}  {}手", name, saved_at, black, white, move_count)
+    }
+
+    fn unreadable_slot(&self, name: &str) -> String { format!("{}  (読み込めません)", name) }
+    fn loaded(&self) -> &'static str { "ロードに成功しました" }
+    fn load_failed(&self, error: &str) -> String { format!("ロードに失敗しました: {}", error) }
+    fn confirm_delete(&self, name: &str) -> String { format!("{}を削除しますか？[y/n]", name) }
+    fn deleted(&self) -> &'static str { "削除しました" }
+    fn delete_failed(&self, error: &str) -> String { format!("削除に失敗しました: {}", error) }
+    fn renamed(&self) -> &'static str { "名前を変更しました" }
+    fn rename_failed(&self, error: &str) -> String { format!("名前の変更に失敗しました: {}", error) }
+
+    fn ask_slot_name(&self, default_name: &str) -> String {
+        format!("保存名を入力してください(空欄なら「{}」、cでやめる)", default_name)
+    }
+
+    fn confirm_overwrite(&self, name: &str) -> String { format!("{}は既にあります。上書きしますか？[y/n]", name) }
+
+    fn wthor_header(&self) -> &'static str { "WTHORデータベースの読み込み" }
+    fn wthor_prompt(&self) -> &'static str { ".wtbファイル名を入力してください" }
+    fn wthor_open_failed(&self, error: &str) -> String { format!("WTHORファイルの読み込みに失敗しました: {}", error) }
+    fn wthor_summary(&self, year: u16, count: usize) -> String { format!("{}年の棋譜 {}局", year, count) }
+    fn wthor_broken(&self, count: usize) -> String { format!("壊れている棋譜が{}局あります", count) }
+    fn wthor_help(&self) -> &'static str { "局番号で選択、対局者名の一部で検索、空行で戻る" }
+    fn wthor_loaded(&self, n: usize, title: &str) -> String { format!("{}局目を読み込みました: {}", n, title) }
+
+    fn wthor_game_title(&self, tournament: &str, black: &str, white: &str, black_score: u8, white_score: i32) -> String {
+        format!("{} 黒: {} 白: {} ({}-{})", tournament, black, white, black_score, white_score)
+    }
+
+    fn wthor_not_found(&self) -> &'static str { "見つかりませんでした" }
+    fn wthor_more(&self, count: usize) -> String { format!("ほか{}局", count) }
+
+    fn error(&self, e: &ReversiError) -> String {
+        match e {
+            ReversiError::OutOfRange { row, col } => format!("{}は盤面の範囲外です", cell_name(*row, *col)),
+            ReversiError::Occupied { row, col } => format!("{}には既に石が置かれています", cell_name(*row, *col)),
+            ReversiError::Blocked { row, col } => format!("{}は石を置けないセルです", cell_name(*row, *col)),
+            ReversiError::NoFlips { row, col } => format!("{}には反転できる石がありません", cell_name(*row, *col)),
+            ReversiError::NotAStone(_) => "石ではありません".to_string(),
+            ReversiError::GameOver => "ゲームは終了しています".to_string(),
+            ReversiError::WrongTurn { expected } => format!("{}の番のはずです", self.turn_name(*expected)),
+            ReversiError::IllegalPass { turn } =>
+                format!("{}は置ける場所があるのにパスしています", self.turn_name(*turn)),
+            ReversiError::MissingPass { turn } =>
+                format!("{}は置ける場所がないのでパスのはずです", self.turn_name(*turn)),
+            ReversiError::NothingToUndo => "戻せる手がありません".to_string(),
+            ReversiError::NothingToRedo => "進められる手がありません".to_string(),
+            ReversiError::MoveNumberOutOfRange { max } => format!("手数は0～{}で指定してください", max),
+            ReversiError::TooManyEmpties { empties, max } =>
+                format!("空きマスが{}個あります。完全読みは空きマスが{}個以下のときだけできます", empties, max),
+            ReversiError::InvalidBoardSize(size) =>
+                format!("盤面の1辺のマス数は{}～{}の偶数にしてください: {}", MIN_BOARD_SIZE, MAX_BOARD_SIZE, size),
+            ReversiError::InvalidHandicap { max } => format!("ハンデの石の数は1～{}で指定してください", max),
+            ReversiError::NoLegalMoves => "どちらも置ける場所がありません".to_string(),
+            ReversiError::NonStandardStart =>
+                "棋譜の形式では8x8の初期配置から始めたゲームしか書き出せません。GGF形式(.ggf)で書き出してください".to_string(),
+            ReversiError::BlockedCellsInGgf =>
+                "置けないセルのある盤面はGGF形式で書き出せません。局面の文字列(p)を使ってください".to_string(),
+            ReversiError::EmptySlotName => "スロット名を入力してください".to_string(),
+            ReversiError::SlotNameTooLong { max } => format!("スロット名は{}文字以内にしてください", max),
+            ReversiError::InvalidSlotName(name) => format!("スロット名に使えない文字が含まれています: {}", name),
+            ReversiError::SlotNotFound(name) => format!("スロットがありません: {}", name),
+            ReversiError::SlotExists(name) => format!("スロットが既にあります: {}", name),
+            ReversiError::Io(e) => format!("ファイルの読み書きに失敗しました: {}", e),
+            ReversiError::Json(e) => format!("JSONの形式が正しくありません: {}", e),
+            ReversiError::Parse(kind) => self.parse_error(kind),
+            ReversiError::At { location, source } => format!("{}: {}", self.location(location), self.error(source)),
+        }
+    }
+
+    fn parse_error(&self, kind: &ParseErrorKind) -> String {
+        match kind {
+            ParseErrorKind::InvalidMove(s) => format!("手の表記が正しくありません: {}", s),
+            ParseErrorKind::InvalidCell(c) => format!("盤面の文字が正しくありません: {}", c),
+            ParseErrorKind::InvalidTurn(s) => format!("手番が正しくありません: {}", s),
+            ParseErrorKind::InvalidNumber(s) => format!("数値が正しくありません: {}", s),
+            ParseErrorKind::InvalidValue { field, value } => format!("{}の値が正しくありません: {}", field, value),
+            ParseErrorKind::InvalidLength { expected, actual } =>
+                format!("{}文字のはずですが、{}文字あります", expected, actual),
+            ParseErrorKind::Truncated => "ファイルが途中で切れています".to_string(),
+            ParseErrorKind::MissingRecords { expected, actual } =>
+                format!("レコードが{}件あるはずですが、{}件しかありません", expected, actual),
+            ParseErrorKind::UndoCountTooLarge { count, moves } =>
+                format!("手数が{}ですが、手が{}個しかありません", count, moves),
+            ParseErrorKind::UnclosedGame => "ゲームが閉じられていません".to_string(),
+            ParseErrorKind::NoGame => "ゲームが見つかりません".to_string(),
+            ParseErrorKind::MalformedProperty(s) => format!("プロパティの形式が正しくありません: {}", s),
+            ParseErrorKind::NotOthello(s) => format!("オセロ以外のゲームです: {}", s),
+            ParseErrorKind::UnsupportedBoardSize(s) => format!("この大きさの盤面には対応していません: {}", s),
+            ParseErrorKind::UnsupportedVersion(version) =>
+                format!("新しい形式(バージョン{})の保存ファイルには対応していません", version),
+            ParseErrorKind::UnsupportedVariant(s) => format!("対応していないルールです: {}", s),
+        }
+    }
+
+    fn location(&self, location: &Location) -> String {
+        match location {
+            Location::Line(line) => format!("行番号={}", line),
+            Location::Move(n) => format!("{}手目", n),
+            Location::Game(n) => format!("{}番目のゲーム", n),
+            Location::Entry { list, index } => format!("{}の{}番目", list, index),
+            Location::Cell { row, col } => format!("{}段{}列", row, col),
+            Location::EndOfFile => "ファイルの最後".to_string(),
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use std::env;
+use std::sync::OnceLock;
+
+use rust_reversi::domain_model::cell::CellState;
+use rust_reversi::domain_model::computer_setting::AiLevel;
+use rust_reversi::domain_model::turn::Turn;
+use rust_reversi::domain_model::variant::Variant;
+use rust_reversi::error::{Location, ParseErrorKind, ReversiError};
+use rust_reversi::service::reversi_service;
+use crate::view::error_message::error_message;
+
+mod ja;
+mod en;
+
+/// 表示する言語
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lang {
+    Japanese,
+    English,
+}
+
+impl Lang {
+    /// "ja"、"en"などの言語コードから。対応していなければNone
+    pub fn from_code(code: &str) -> Option<Lang> {
+        let code = code.trim().to_lowercase();
+        if code.starts_with("ja") {
+            Some(Lang::Japanese)
+        } else if code.starts_with("en") {
+            Some(Lang::English)
+        } else {
+            None
+        }
+    }
+
+    /// 環境変数LC_ALL、LC_MESSAGES、LANGの順に見て決める。
+    /// 設定がないかC/POSIXなら日本語、日本語以外のロケールなら英語
+    pub fn from_env() -> Lang {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty());
+        match locale.as_deref() {
+            None | Some("C") | Some("POSIX") => Lang::Japanese,
+            Some(locale) if locale.starts_with("C.") => Lang::Japanese,
+            Some(locale) => Lang::from_code(locale).unwrap_or(Lang::English),
+        }
+    }
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// 表示する言語を決める。最初に1回だけ呼ぶ
+pub fn set_lang(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+/// コマンドラインの指定、設定ファイル、環境変数の順に見て、表示する言語を決める。
+/// 指定された言語に対応していなければ終了する
+pub fn init(option: Option<&str>) {
+    let config = reversi_service::load_config();
+    let config_lang = config.as_ref().ok().and_then(|config| config.lang.clone());
+    let lang = option.and_then(Lang::from_code)
+        .or_else(|| config_lang.as_deref().and_then(Lang::from_code))
+        .unwrap_or_else(Lang::from_env);
+    set_lang(lang);
+
+    if let Some(code) = option.filter(|code| Lang::from_code(code).is_none()) {
+        println!("{}", messages().unknown_lang(code));
+        println!("{}", messages().usage());
+        std::process::exit(1);
+    }
+    match config {
+        Ok(_) => {
+            if let Some(code) = config_lang.filter(|code| Lang::from_code(code).is_none()) {
+                println!("{}", messages().unknown_lang(&code));
+            }
+        },
+        Err(e) => println!("{}", messages().config_failed(&error_message(&e))),
+    }
+}
+
+/// 現在の言語のメッセージ。言語を決めていなければ環境変数から決める
+pub fn messages() -> &'static dyn Messages {
+    match LANG.get_or_init(Lang::from_env) {
+        Lang::Japanese => &ja::Japanese,
+        Lang::English => &en::English,
+    }
+}
+
+/// 画面に表示する文言の一覧。言語ごとに実装する
+pub trait Messages: Sync {
+    // 共通
+    fn turn_name(&self, turn: Turn) -> &'static str;
+    fn stone(&self, cell: CellState) -> &'static str; // 盤面の表示。全角1文字分の幅
+    fn level_name(&self, level: AiLevel) -> &'static str;
+    fn invalid_input(&self) -> &'static str;
+    fn enter_range(&self, max: usize) -> String;
+    fn pass(&self) -> &'static str;
+    fn human(&self) -> &'static str;
+    fn computer(&self, level: AiLevel) -> String;
+    fn usage(&self) -> &'static str;
+    fn unknown_lang(&self, code: &str) -> String;
+    fn config_failed(&self, error: &str) -> String;
+
+    // タイトル画面
+    fn app_title(&self) -> &'static str;
+    fn menu_header(&self) -> &'static str;
+    fn menu_items(&self) -> &'static [&'static str];
+    fn new_game_started(&self) -> &'static str;
+    fn computer_game_started(&self) -> &'static str;
+    fn import_header(&self) -> &'static str;
+    fn import_prompt(&self) -> &'static str;
+    fn imported(&self) -> &'static str;
+    fn import_failed(&self, error: &str) -> String;
+    fn app_quit(&self) -> &'static str;
+    fn interrupted_header(&self) -> &'static str;
+    fn resume_prompt(&self) -> &'static str;
+    fn enter_y_or_n(&self) -> &'static str;
+    fn interrupted_discarded(&self) -> &'static str;
+    fn resuming(&self) -> &'static str;
+    fn resume_failed(&self, error: &str) -> String;
+    fn ask_player_name(&self, turn: Turn) -> String;
+    fn ask_board_size(&self, min: usize, max: usize, default: usize) -> String;
+    fn variant_header(&self) -> &'static str;
+    fn variant_name(&self, variant: Variant) -> &'static str;
+    fn rule(&self, variant: Variant) -> String;
+    fn start_header(&self) -> &'static str;
+    fn start_items(&self) -> &'static [&'static str];
+    fn handicap_turn_header(&self) -> &'static str;
+    fn ask_handicap_corners(&self, max: usize) -> String;
+    fn position_prompt(&self) -> &'static str;
+    fn layout_prompt(&self) -> &'static str;
+    fn start_failed(&self, error: &str) -> String;
+
+    // 盤面エディタ
+    fn editor_header(&self) -> &'static str;
+    fn editor_help(&self) -> &'static [&'static str];
+    fn editor_turn(&self, turn: Turn) -> String;
+    fn editor_cell_example(&self) -> &'static str;
+    fn editor_no_stone(&self, cell: &str) -> String;
+    fn editor_playable(&self) -> &'static str;
+    fn editor_game_started(&self) -> &'static str;
+    fn opponent_header(&self) -> &'static str;
+    fn opponent_items(&self) -> &'static [&'static str];
+    fn suggested_move(&self, turn: Turn, cell: &str) -> String;
+    fn computer_turn_header(&self) -> &'static str;
+    fn computer_turn_items(&self) -> &'static [&'static str];
+    fn enter_1_or_2(&self) -> &'static str;
+    fn computer_level_header(&self) -> &'static str;
+
+    // ゲーム画面
+    fn turn_header(&self, turn: Turn) -> String;
+    fn turn_header_with_player(&self, turn: Turn, player: &str) -> String;
+    fn review_header(&self) -> &'static str;
+    fn stone_placed(&self, turn: Turn, row: usize, col: usize) -> String;
+    fn saved(&self, slot: &str) -> String;
+    fn save_failed(&self, error: &str) -> String;
+    fn game_quit(&self) -> &'static str;
+    fn autosave_failed(&self, error: &str) -> String;
+    fn autosave_delete_failed(&self, error: &str) -> String;
+    fn passed(&self, turn: Turn) -> String;
+    fn illegal_action_forfeit(&self, turn: Turn) -> String;
+    fn legal_moves(&self, cells: &str) -> String;
+    fn col_axis(&self) -> &'static str;
+    fn row_axis(&self) -> &'static str;
+    fn game_over_header(&self) -> &'static str;
+    fn board_full(&self) -> &'static str;
+    fn no_moves(&self) -> &'static str;
+    fn resigned(&self, turn: Turn) -> String;
+    fn timed_out(&self, turn: Turn) -> String;
+    fn disc_counts(&self, black: i16, white: i16, empty: i16) -> String;
+    fn winner(&self, turn: Turn) -> String;
+    fn draw(&self) -> &'static str;
+    fn analyzing(&self) -> &'static str;
+    fn perfect_play_win(&self, turn: Turn, disc_diff: i32) -> String;
+    fn perfect_play_draw(&self) -> &'static str;
+    fn best_line(&self, line: &str) -> String;
+    fn history_header(&self) -> &'static str;
+    fn history_start(&self) -> &'static str;
+    fn history_current(&self) -> &'static str;
+    fn ggf_players(&self, black: &str, white: &str) -> String;
+    fn ggf_place_date(&self, place: &str, date: &str) -> String;
+    fn ggf_time_control(&self, time_control: &str) -> String;
+    fn ggf_result(&self, result: &str) -> String;
+
+    // 人のプレーヤーの操作
+    fn save_prompt(&self) -> &'static str;
+    fn enter_ync(&self) -> &'static str;
+    fn game_continued(&self) -> &'static str;
+    fn review_help(&self) -> &'static str;
+    fn move_example(&self) -> &'static str;
+    fn move_example_short(&self) -> &'static str;
+    fn command_help(&self) -> &'static [&'static str];
+    fn move_string(&self, moves: &str) -> String;
+    fn position_string(&self, position: &str) -> String;
+    fn exported(&self, path: &str) -> String;
+    fn export_failed(&self, error: &str) -> String;
+    fn move_number_too_large(&self) -> &'static str;
+
+    // 保存したゲームの一覧
+    fn saves_header(&self) -> &'static str;
+    fn saves_load_failed(&self, error: &str) -> String;
+    fn saves_help(&self) -> &'static str;
+    fn no_saves(&self) -> &'static str;
+    fn legacy_save(&self, file_name: &str) -> String;
+    fn slot_summary(&self, name: &str, saved_at: &str, black: &str, white: &str, move_count: usize) -> String;
+    fn unreadable_slot(&self, name: &str) -> String;
+    fn loaded(&self) -> &'static str;
+    fn load_failed(&self, error: &str) -> String;
+    fn confirm_delete(&self, name: &str) -> String;
+    fn deleted(&self) -> &'static str;
+    fn delete_failed(&self, error: &str) -> String;
+    fn renamed(&self) -> &'static str;
+    fn rename_failed(&self, error: &str) -> String;
+    fn ask_slot_name(&self, default_name: &str) -> String;
+    fn confirm_overwrite(&self, name: &str) -> String;
+
+    // WTHORデータベース
+    fn wthor_header(&self) -> &'static str;
+    fn wthor_prompt(&self) -> &'static str;
+    fn wthor_open_failed(&self, error: &str) -> String;
+    fn wthor_summary(&self, year: u16, count: usize) -> String;
+    fn wthor_broken(&self, count: usize) -> String;
+    fn wthor_help(&self) -> &'static str;
+    fn wthor_loaded(&self, n: usize, title: &str) -> String;
+    fn wthor_game_title(&self, tournament: &str, black: &str, white: &str, black_score: u8, white_score: i32) -> String;
+    fn wthor_not_found(&self) -> &'static str;
+    fn wthor_more(&self, count: usize) -> String;
+
+    // エラー
+    fn error(&self, e: &ReversiError) -> String;
+    fn parse_error(&self, kind: &ParseErrorKind) -> String;
+    fn location(&self, location: &Location) -> String;
+}
@@ -1,3 +1,9 @@
 pub mod view_util;
+pub mod error_message;
+pub mod messages;
 pub mod title_view;
 pub mod game_view;
+pub mod console_player;
+pub mod wthor_view;
+pub mod save_list_view;
+pub mod editor_view;
@@ -0,0 +1,143 @@
+use std::io;
+use regex::Regex;
+
+use rust_reversi::data::fileio::SaveSlot;
+use rust_reversi::domain_model::reversi_state::ReversiState;
+use rust_reversi::service::reversi_service;
+use crate::view::view_util::show_header2;
+use crate::view::error_message::error_message;
+use crate::view::messages::messages;
+
+/// 保存したゲームの一覧を表示し、選んだゲームを読み込んで返す。
+/// 一覧ではスロットの削除と名前の変更もできる。やめた場合はNone
+pub fn show() -> Option<ReversiState> {
+    let regex_delete = Regex::new(r"^d\s+([0-9]+)$").unwrap();
+    let regex_rename = Regex::new(r"^r\s+([0-9]+)\s+(.+)$").unwrap();
+
+    loop {
+        let slots = match reversi_service::list_saves() {
+            Ok(slots) => slots,
+            Err(e) => {
+                println!("{}", messages().saves_load_failed(&error_message(&e)));
+                return None;
+            }
+        };
+        let legacy = reversi_service::legacy_save_name();
+        show_slots(&slots, legacy);
+        if slots.is_empty() && legacy.is_none() {
+            return None;
+        }
+
+        println!("{}", messages().saves_help());
+        let input = read_line();
+        if input.is_empty() {
+            return None;
+        }
+
+        // 以前の保存ファイル
+        if input == "0" && legacy.is_some() {
+            match reversi_service::load_legacy() {
+                Ok(state) => {
+                    println!("{}", messages().loaded());
+                    return Some(state);
+                },
+                Err(e) => println!("{}", messages().load_failed(&error_message(&e))),
+            }
+            continue;
+        }
+
+        if let Ok(n) = input.parse::<usize>() {
+            match select(&slots, n) {
+                Some(slot) => match reversi_service::load(&slot.name) {
+                    Ok(state) => {
+                        println!("{}", messages().loaded());
+                        return Some(state);
+                    },
+                    Err(e) => println!("{}", messages().load_failed(&error_message(&e))),
+                },
+                None => println!("{}", messages().enter_range(slots.len())),
+            }
+        } else if let Some(c) = regex_delete.captures(&input) {
+            if let Some(slot) = c[1].parse().ok().and_then(|n| select(&slots, n)) {
+                println!("{}", messages().confirm_delete(&slot.name));
+                if read_line() == "y" {
+                    match reversi_service::delete_save(&slot.name) {
+                        Ok(_) => println!("{}", messages().deleted()),
+                        Err(e) => println!("{}", messages().delete_failed(&error_message(&e))),
+                    }
+                }
+            } else {
+                println!("{}", messages().enter_range(slots.len()));
+            }
+        } else if let Some(c) = regex_rename.captures(&input) {
+            if let Some(slot) = c[1].parse().ok().and_then(|n| select(&slots, n)) {
+                match reversi_service::rename_save(&slot.name, c[2].trim()) {
+                    Ok(_) => println!("{}", messages().renamed()),
+                    Err(e) => println!("{}", messages().rename_failed(&error_message(&e))),
+                }
+            } else {
+                println!("{}", messages().enter_range(slots.len()));
+            }
+        } else {
+            println!("{}", messages().invalid_input());
+        }
+    }
+}
+
+/// 保存するスロット名を尋ねる。別のゲームを上書きする場合は確認する。やめた場合はNone
+pub fn ask_slot_name(state: &ReversiState) -> Option<String> {
+    let default_name = reversi_service::default_save_name(state);
+    loop {
+        println!("{}", messages().ask_slot_name(&default_name));
+        let input = read_line();
+        let name = match input.as_str() {
+            "" => default_name.clone(),
+            "c" => return None,
+            _ => input,
+        };
+        if let Err(e) = reversi_service::validate_save_name(&name) {
+            println!("{}", error_message(&e));
+            continue;
+        }
+        if name != state.info.slot && reversi_service::save_exists(&name) {
+            println!("{}", messages().confirm_overwrite(&name));
+            if read_line() != "y" {
+                continue;
+            }
+        }
+        return Some(name);
+    }
+}
+
+fn show_slots(slots: &[SaveSlot], legacy: Option<&str>) {
+    show_header2(messages().saves_header());
+    if slots.is_empty() && legacy.is_none() {
+        println!("{}", messages().no_saves());
+        return;
+    }
+    if let Some(legacy) = legacy {
+        println!("{:>3}. {}", 0, messages().legacy_save(legacy));
+    }
+    for (i, slot) in slots.iter().enumerate() {
+        match &slot.summary {
+            Some(summary) => println!("{:>3}. {}", i + 1, messages().slot_summary(&slot.name,
+                &summary.saved_at, player_name(&summary.black_player), player_name(&summary.white_player),
+                summary.move_count)),
+            None => println!("{:>3}. {}", i + 1, messages().unreadable_slot(&slot.name)),
+        }
+    }
+}
+
+fn player_name(name: &str) -> &str {
+    if name.is_empty() { "-" } else { name }
+}
+
+fn select(slots: &[SaveSlot], n: usize) -> Option<&SaveSlot> {
+    slots.get(n.wrapping_sub(1))
+}
+
+fn read_line() -> String {
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).unwrap();
+    line.trim().to_string()
+}
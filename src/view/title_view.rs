@@ -1,21 +1,32 @@
 use std::io;
+use std::sync::OnceLock;
+use regex::Regex;
 
-use crate::view::view_util::{show_header1, show_header2};
-use crate::view::game_view;
-use crate::domain_model::reversi_state::ReversiState;
-use crate::service::reversi_service;
+use crate::view::view_util::{is_ggf_path, show_header1, show_header2};
+use crate::view::{editor_view, game_view, save_list_view, wthor_view};
+use crate::view::error_message::error_message;
+use crate::view::messages::messages;
+use rust_reversi::domain_model::board::{Board, DEFAULT_BOARD_SIZE, MIN_BOARD_SIZE, MAX_BOARD_SIZE};
+use rust_reversi::domain_model::handicap::{Handicap, MAX_HANDICAP_CORNERS};
+use rust_reversi::domain_model::reversi_state::ReversiState;
+use rust_reversi::service::reversi_service;
+use rust_reversi::domain_model::turn::Turn;
+use rust_reversi::domain_model::variant::Variant;
+use rust_reversi::domain_model::computer_setting::{AiLevel, ComputerSetting};
+use rust_reversi::error::ReversiError;
 
 pub fn show() {
-    show_header1("リバーシ");
+    show_header1(messages().app_title());
+    resume_interrupted_game();
 
     // タイトル画面のメインループ
     loop {
-        show_header2("メニュー");
-        println!("1. 新規ゲーム開始");
-        println!("2. つづきから");
-        println!("9. 終了");
+        show_header2(messages().menu_header());
+        for item in messages().menu_items() {
+            println!("{}", item);
+        }
 
-        let error_message = "1～9を入力してください";
+        let input_error = messages().enter_range(9);
         // 入力ループ
         'input_loop: loop {
             let mut user_input = String::new();
@@ -23,40 +34,296 @@ pub fn show() {
             let selection: i32 = match user_input.trim().parse() {
                 Ok(n) => n,
                 Err(_) => {
-                    println!("{}", error_message);
+                    println!("{}", input_error);
                     continue;
                 }
             };
 
             match selection {
                 1 => {
-                    println!("新規ゲームを開始します");
-                    let mut state = ReversiState::new();
+                    let mut state = create_game();
+                    state.info.black_player = ask_player_name(Turn::Black);
+                    state.info.white_player = ask_player_name(Turn::White);
+                    println!("{}", messages().new_game_started());
                     game_view::show(&mut state);
                     break 'input_loop;
                 },
                 2 => {
-                    println!("前回の状態をロードします");
-                    match reversi_service::load() {
-                        Ok(state) => { 
-                            println!("ロードに成功しました"); 
-                            let mut s = state;
-                            game_view::show(&mut s);
+                    if let Some(mut state) = save_list_view::show() {
+                        game_view::show(&mut state);
+                    }
+                    break 'input_loop;
+                },
+                3 => {
+                    let turn = select_computer_turn();
+                    let level = select_computer_level();
+                    let mut state = create_game();
+                    state.computer = Some(ComputerSetting { turn, level });
+                    let human_turn = if turn == Turn::Black { Turn::White } else { Turn::Black };
+                    let name = ask_player_name(human_turn);
+                    match human_turn {
+                        Turn::Black => state.info.black_player = name,
+                        Turn::White => state.info.white_player = name,
+                    }
+                    println!("{}", messages().computer_game_started());
+                    game_view::show(&mut state);
+                    break 'input_loop;
+                },
+                4 => {
+                    show_header2(messages().import_header());
+                    println!("{}", messages().import_prompt());
+                    let mut source = String::new();
+                    io::stdin().read_line(&mut source).unwrap();
+                    match import_game(source.trim()) {
+                        Ok(mut state) => {
+                            println!("{}", messages().imported());
+                            game_view::show(&mut state);
                             break 'input_loop;
                         },
-                        Err(e) => { 
-                            println!("ロードに失敗しました: {}", e.message) 
+                        Err(e) => {
+                            println!("{}", messages().import_failed(&error_message(&e)))
                         }
                     }
                 },
+                5 => {
+                    if let Some(mut state) = wthor_view::select_game() {
+                        game_view::show(&mut state);
+                    }
+                    break 'input_loop;
+                },
+                6 => {
+                    if let Some(mut state) = editor_view::edit() {
+                        println!("{}", messages().editor_game_started());
+                        game_view::show(&mut state);
+                    }
+                    break 'input_loop;
+                },
                 9 => {
-                    println!("アプリを終了します");
+                    println!("{}", messages().app_quit());
                     std::process::exit(0); // TODO: ここでいきなりexitっていいのかね？
                 },
                 _ => {
-                    println!("{}", error_message);
+                    println!("{}", input_error);
                 }
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// 前回のゲームが途中で終了していれば、再開するか尋ねる
+fn resume_interrupted_game() {
+    if !reversi_service::has_autosave() {
+        return;
+    }
+
+    show_header2(messages().interrupted_header());
+    println!("{}", messages().resume_prompt());
+    loop {
+        let mut user_input = String::new();
+        // 入力が終わっていれば、自動保存を残したままにする
+        if io::stdin().read_line(&mut user_input).unwrap() == 0 {
+            return;
+        }
+        match user_input.trim().to_lowercase().as_str() {
+            "y" | "yes" => break,
+            "n" | "no" => {
+                match reversi_service::discard_autosave() {
+                    Ok(_) => println!("{}", messages().interrupted_discarded()),
+                    Err(e) => println!("{}", messages().autosave_delete_failed(&error_message(&e))),
+                }
+                return;
+            },
+            _ => println!("{}", messages().enter_y_or_n()),
+        }
+    }
+
+    match reversi_service::load_autosave() {
+        Ok(mut state) => {
+            println!("{}", messages().resuming());
+            game_view::show(&mut state);
+        },
+        Err(e) => println!("{}", messages().resume_failed(&error_message(&e))),
+    }
+}
+
+/// 人のプレーヤーの名前を尋ねる。空欄なら空文字列を返し、既定の名前にする
+fn ask_player_name(turn: Turn) -> String {
+    println!("{}", messages().ask_player_name(turn));
+    let mut user_input = String::new();
+    io::stdin().read_line(&mut user_input).unwrap();
+    user_input.trim().to_string()
+}
+
+/// ルールと開始局面を尋ね、その局面から始めるゲーム状態を作る
+fn create_game() -> ReversiState {
+    let variant = select_variant();
+    show_header2(messages().start_header());
+    for item in messages().start_items() {
+        println!("{}", item);
+    }
+
+    loop {
+        let mut user_input = String::new();
+        io::stdin().read_line(&mut user_input).unwrap();
+        let result = match user_input.trim() {
+            "" | "1" => reversi_service::new_game(select_board_size(), variant),
+            "2" => {
+                let size = select_board_size();
+                let handicap = select_handicap();
+                reversi_service::new_handicap_game(size, variant, handicap)
+            },
+            "3" => {
+                println!("{}", messages().position_prompt());
+                let mut position = String::new();
+                io::stdin().read_line(&mut position).unwrap();
+                reversi_service::new_game_from_position(position.trim(), variant)
+            },
+            "4" => {
+                println!("{}", messages().layout_prompt());
+                let mut path = String::new();
+                io::stdin().read_line(&mut path).unwrap();
+                reversi_service::new_game_from_layout(path.trim(), variant)
+            },
+            _ => {
+                println!("{}", messages().enter_range(messages().start_items().len()));
+                continue;
+            },
+        };
+        match result {
+            Ok(state) => return state,
+            Err(e) => println!("{}", messages().start_failed(&error_message(&e))),
+        }
+    }
+}
+
+/// 盤面の大きさを尋ねる。空欄なら8x8
+fn select_board_size() -> usize {
+    println!("{}", messages().ask_board_size(MIN_BOARD_SIZE, MAX_BOARD_SIZE, DEFAULT_BOARD_SIZE));
+    loop {
+        let mut user_input = String::new();
+        io::stdin().read_line(&mut user_input).unwrap();
+        match user_input.trim() {
+            "" => return DEFAULT_BOARD_SIZE,
+            input => match input.parse::<usize>() {
+                Ok(size) if Board::is_valid_size(size) => return size,
+                Ok(size) => println!("{}", error_message(&ReversiError::InvalidBoardSize(size))),
+                Err(_) => println!("{}", messages().ask_board_size(MIN_BOARD_SIZE, MAX_BOARD_SIZE, DEFAULT_BOARD_SIZE)),
+            },
+        }
+    }
+}
+
+/// ハンデを受ける色と、隅に置く石の数を尋ねる
+fn select_handicap() -> Handicap {
+    show_header2(messages().handicap_turn_header());
+    for item in messages().computer_turn_items() {
+        println!("{}", item);
+    }
+    let turn = loop {
+        let mut user_input = String::new();
+        io::stdin().read_line(&mut user_input).unwrap();
+        match user_input.trim() {
+            "1" => break Turn::Black,
+            "2" => break Turn::White,
+            _ => println!("{}", messages().enter_1_or_2()),
+        }
+    };
+
+    println!("{}", messages().ask_handicap_corners(MAX_HANDICAP_CORNERS));
+    loop {
+        let mut user_input = String::new();
+        io::stdin().read_line(&mut user_input).unwrap();
+        match user_input.trim().parse::<usize>() {
+            Ok(corners) if (1..=MAX_HANDICAP_CORNERS).contains(&corners) => return Handicap { turn, corners },
+            _ => println!("{}", error_message(&ReversiError::InvalidHandicap { max: MAX_HANDICAP_CORNERS })),
+        }
+    }
+}
+
+/// ルールを選ぶ。空欄なら通常のルール
+pub fn select_variant() -> Variant {
+    show_header2(messages().variant_header());
+    for (i, variant) in Variant::ALL.iter().enumerate() {
+        println!("{}. {}", i + 1, messages().variant_name(*variant));
+    }
+
+    loop {
+        let mut user_input = String::new();
+        io::stdin().read_line(&mut user_input).unwrap();
+        if user_input.trim().is_empty() {
+            return Variant::Standard;
+        }
+        match user_input.trim().parse::<usize>() {
+            Ok(n) if (1..=Variant::ALL.len()).contains(&n) => return Variant::ALL[n - 1],
+            _ => println!("{}", messages().enter_range(Variant::ALL.len())),
+        }
+    }
+}
+
+/// コンピュータが打つ色を選ぶ
+pub fn select_computer_turn() -> Turn {
+    show_header2(messages().computer_turn_header());
+    for item in messages().computer_turn_items() {
+        println!("{}", item);
+    }
+
+    loop {
+        let mut user_input = String::new();
+        io::stdin().read_line(&mut user_input).unwrap();
+        match user_input.trim() {
+            "1" => return Turn::Black,
+            "2" => return Turn::White,
+            _ => println!("{}", messages().enter_1_or_2()),
+        }
+    }
+}
+
+/// コンピュータの強さを選ぶ
+pub fn select_computer_level() -> AiLevel {
+    show_header2(messages().computer_level_header());
+    for (i, level) in AiLevel::ALL.iter().enumerate() {
+        println!("{}. {}", i + 1, messages().level_name(*level));
+    }
+
+    loop {
+        let mut user_input = String::new();
+        io::stdin().read_line(&mut user_input).unwrap();
+        match user_input.trim().parse::<usize>() {
+            Ok(n) if (1..=AiLevel::ALL.len()).contains(&n) => return AiLevel::ALL[n - 1],
+            _ => println!("{}", messages().enter_range(AiLevel::ALL.len())),
+        }
+    }
+}
+
+// import_gameで使う正規表現。最初に使うときに1回だけ作る
+static REGEX_MOVES: OnceLock<Regex> = OnceLock::new();
+static REGEX_POSITION: OnceLock<Regex> = OnceLock::new();
+
+/// 棋譜の文字列、棋譜ファイル名、局面の文字列のいずれかから、ゲーム状態を作る
+fn import_game(source: &str) -> Result<ReversiState, ReversiError> {
+    let regex_moves = REGEX_MOVES.get_or_init(|| Regex::new(r"^([a-pA-P][0-9]{1,2}\s*)+$").unwrap());
+    let regex_position = REGEX_POSITION
+        .get_or_init(|| Regex::new(r"^[XOxo.#\-\s]{16,}[XOxo]\s*(;.*)?$").unwrap());
+    if regex_moves.is_match(source) {
+        reversi_service::import_move_string(source)
+    } else if regex_position.is_match(source) {
+        reversi_service::import_position(source)
+    } else if is_ggf_path(source) {
+        let (game, state) = reversi_service::import_ggf_file(source)?;
+        game_view::show_ggf_info(&game);
+        Ok(state)
+    } else {
+        reversi_service::import_move_file(source)
+    }
+}
+
+/// コマンドライン引数で指定された棋譜からゲームを始める
+pub fn show_imported_game(result: Result<ReversiState, ReversiError>) {
+    match result {
+        Ok(mut state) => {
+            println!("{}", messages().imported());
+            game_view::show(&mut state);
+        },
+        Err(e) => println!("{}", messages().import_failed(&error_message(&e))),
+    }
+}
@@ -1,3 +1,10 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use rust_reversi::domain_model::turn::Turn;
+use crate::view::messages::messages;
+
 pub fn show_header1(header: &str) {
     println!();
     println!("========================================");
@@ -9,4 +16,35 @@ pub fn show_header2(header: &str) {
     println!();
     println!("{}", header);
     println!("----------------------------------------");
-}
\ No newline at end of file
+}
+
+pub fn turn_name(turn: Turn) -> &'static str {
+    messages().turn_name(turn)
+}
+
+// parse_cellで使う正規表現。最初に使うときに1回だけ作る
+static REGEX_CELL: OnceLock<Regex> = OnceLock::new();
+static REGEX_CELL_SEPARATED: OnceLock<Regex> = OnceLock::new();
+
+/// "43"や"10-3"のようなセルの入力を段と列にする。形式が違えばNone
+pub fn parse_cell(text: &str) -> Option<(usize, usize)> {
+    let regex_cell = REGEX_CELL.get_or_init(|| Regex::new(r"^([1-9])([1-9])$").unwrap());
+    let regex_cell_separated = REGEX_CELL_SEPARATED
+        .get_or_init(|| Regex::new(r"^([0-9]{1,2})[\s-]+([0-9]{1,2})$").unwrap());
+    let c = regex_cell.captures(text.trim()).or_else(|| regex_cell_separated.captures(text.trim()))?;
+    Some((c[1].parse().unwrap(), c[2].parse().unwrap()))
+}
+
+/// GGF形式のファイル名か
+pub fn is_ggf_path(path: &str) -> bool {
+    path.to_lowercase().ends_with(".ggf")
+}
+
+/// セルの表記。入力と同じく"43"とし、10以上の段や列があれば"10-3"のように区切る
+pub fn cell_name(row: usize, col: usize) -> String {
+    if row < 10 && col < 10 {
+        format!("{}{}", row, col)
+    } else {
+        format!("{}-{}", row, col)
+    }
+}
@@ -0,0 +1,88 @@
+use std::io;
+
+use rust_reversi::data::wthor::{WthorDatabase, WthorGame};
+use rust_reversi::domain_model::reversi_state::ReversiState;
+use rust_reversi::service::reversi_service;
+use crate::view::view_util::show_header2;
+use crate::view::error_message::error_message;
+use crate::view::messages::messages;
+
+// 一度に一覧表示する局数
+const LIST_SIZE: usize = 20;
+
+/// WTHORの.wtbファイルを開き、選んだ1局のゲーム状態を返す。やめた場合はNone
+pub fn select_game() -> Option<ReversiState> {
+    show_header2(messages().wthor_header());
+    println!("{}", messages().wthor_prompt());
+    let path = read_line();
+    let database = match reversi_service::open_wthor_database(&path) {
+        Ok(database) => database,
+        Err(e) => {
+            println!("{}", messages().wthor_open_failed(&error_message(&e)));
+            return None;
+        }
+    };
+    show_summary(&database);
+
+    loop {
+        println!("{}", messages().wthor_help());
+        let input = read_line();
+        if input.is_empty() {
+            return None;
+        }
+
+        match input.parse::<usize>() {
+            Ok(n) => match database.games.get(n.wrapping_sub(1)) {
+                Some(Ok(game)) => match reversi_service::replay_wthor_game(game) {
+                    Ok(state) => {
+                        println!("{}", messages().wthor_loaded(n, &game_title(game)));
+                        return Some(state);
+                    },
+                    Err(e) => println!("{}", error_message(&e)),
+                },
+                Some(Err(e)) => println!("{}", error_message(e)),
+                None => println!("{}", messages().enter_range(database.games.len())),
+            },
+            Err(_) => show_matches(&database, &input),
+        }
+    }
+}
+
+fn show_summary(database: &WthorDatabase) {
+    let broken = database.games.iter().filter(|game| game.is_err()).count();
+    println!("{}", messages().wthor_summary(database.header.year, database.games.len()));
+    if broken > 0 {
+        println!("{}", messages().wthor_broken(broken));
+    }
+}
+
+/// 対局者名にkeywordを含む局を表示する
+fn show_matches(database: &WthorDatabase, keyword: &str) {
+    let keyword = keyword.to_lowercase();
+    let matches: Vec<(usize, &WthorGame)> = database.games.iter()
+        .enumerate()
+        .filter_map(|(i, game)| game.as_ref().ok().map(|game| (i + 1, game)))
+        .filter(|(_, game)| game.black_player.to_lowercase().contains(&keyword)
+            || game.white_player.to_lowercase().contains(&keyword))
+        .collect();
+
+    for (n, game) in matches.iter().take(LIST_SIZE) {
+        println!("{:>6}. {}", n, game_title(game));
+    }
+    if matches.is_empty() {
+        println!("{}", messages().wthor_not_found());
+    } else if matches.len() > LIST_SIZE {
+        println!("{}", messages().wthor_more(matches.len() - LIST_SIZE));
+    }
+}
+
+fn game_title(game: &WthorGame) -> String {
+    messages().wthor_game_title(&game.tournament, &game.black_player, &game.white_player,
+        game.black_score, 64 - game.black_score as i32)
+}
+
+fn read_line() -> String {
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).unwrap();
+    line.trim().to_string()
+}